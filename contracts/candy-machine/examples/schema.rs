@@ -4,8 +4,8 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use candy_machine::msg::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, IsWhitelistedResponse, MintStage,
-    MintStagesResponse, QueryMsg, StateResponse,
+    ConfigResponse, ExecuteMsg, InstantiateMsg, IsWhitelistedResponse, MigrateMsg, MintStage,
+    MintStagesResponse, QueryMsg, ReservationResponse, StateResponse, UserMintsResponse,
 };
 use candy_machine::state::config::Config;
 use candy_machine::state::state::State;
@@ -19,6 +19,7 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
     export_schema(&schema_for!(Config), &out_dir);
     export_schema(&schema_for!(State), &out_dir);
     export_schema(&schema_for!(MintStage), &out_dir);
@@ -26,4 +27,6 @@ fn main() {
     export_schema(&schema_for!(StateResponse), &out_dir);
     export_schema(&schema_for!(IsWhitelistedResponse), &out_dir);
     export_schema(&schema_for!(MintStagesResponse), &out_dir);
+    export_schema(&schema_for!(UserMintsResponse), &out_dir);
+    export_schema(&schema_for!(ReservationResponse), &out_dir);
 }
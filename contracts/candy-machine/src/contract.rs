@@ -11,10 +11,18 @@ use serde::Serialize;
 
 use crate::error::ContractError;
 use crate::handlers::configure;
+use crate::handlers::migrate;
 use crate::handlers::mint;
+use crate::handlers::ownership;
+use crate::handlers::pause;
 use crate::handlers::query;
+use crate::handlers::rebate;
+use crate::handlers::refund;
+use crate::handlers::reveal;
+use crate::handlers::shuffle;
+use crate::handlers::validation::validate_mint_stages;
 use crate::handlers::withdraw;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::response::MsgInstantiateContractResponse;
 use crate::state::collection_kind;
 use crate::state::config::{Config, CONFIG};
@@ -36,17 +44,29 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    validate_mint_stages(deps.api, &msg.mint_stages)?;
+
     let config = Config {
         owner: info.sender.clone(),
+        pending_owner: None,
+        pauser: None,
         name: msg.name.clone(),
         description: msg.description,
         collection_kind: collection_kind::to_raw(msg.collection_kind, deps.api)?,
         nft_address: None,
         max_token_count: msg.max_token_count,
+        max_per_user: msg.max_per_user,
+        reservation_deadline: None,
+        team_reserve: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
-    let state = State { token_count: 0 };
+    let state = State {
+        token_count: 0,
+        paused: false,
+        airdropped: 0,
+        queued: 0,
+    };
     STATE.save(deps.storage, &state)?;
 
     msg.mint_stages
@@ -103,26 +123,87 @@ pub fn execute(
     match msg {
         ExecuteMsg::Mint {
             stage_id,
-            signature,
-        } => mint::mint(deps, env, info, stage_id, signature),
+            quantity,
+            signatures,
+            merkle_proof,
+        } => mint::mint(
+            deps,
+            env,
+            info,
+            stage_id,
+            quantity,
+            signatures,
+            merkle_proof,
+        ),
         ExecuteMsg::Reserve {
             stage_id,
-            signature,
-        } => mint::mint(deps, env, info, stage_id, signature),
+            quantity,
+            signatures,
+            merkle_proof,
+        } => mint::reserve(
+            deps,
+            env,
+            info,
+            stage_id,
+            quantity,
+            signatures,
+            merkle_proof,
+        ),
         ExecuteMsg::MinterMint { token_id, metadata } => {
             mint::minter_mint(deps, info, token_id, metadata)
         }
+        ExecuteMsg::MinterMintBatch { items } => mint::minter_mint_batch(deps, info, items),
         ExecuteMsg::ConfigureMintStage { id, config } => {
             configure::configure_mint_stage(deps, info, id, config)
         }
-        ExecuteMsg::WithdrawFunds { recipient } => {
-            withdraw::withdraw_funds(deps, env, info, recipient)
+        ExecuteMsg::ConfigurePublicKey { config } => {
+            configure::configure_public_key(deps, env, info, config)
         }
+        ExecuteMsg::ConfigureShuffle { config } => {
+            shuffle::configure_shuffle(deps, env, info, config)
+        }
+        ExecuteMsg::AddMintStage { stage } => configure::add_mint_stage(deps, info, stage),
+        ExecuteMsg::RemoveMintStage { id } => configure::remove_mint_stage(deps, info, id),
+        ExecuteMsg::WithdrawFunds {
+            recipient,
+            amount,
+            cw20_tokens,
+        } => withdraw::withdraw_funds(deps, env, info, recipient, amount, cw20_tokens),
+        ExecuteMsg::Receive(cw20_msg) => mint::receive_cw20(deps, env, info, cw20_msg),
+        ExecuteMsg::ProposeNewOwner { owner } => ownership::propose_new_owner(deps, info, owner),
+        ExecuteMsg::AcceptOwnership {} => ownership::accept_ownership(deps, info),
+        ExecuteMsg::CancelOwnershipProposal {} => ownership::cancel_ownership_proposal(deps, info),
         ExecuteMsg::Configure {
             name,
             description,
             nft_address,
-        } => configure::configure(deps, info, name, description, nft_address),
+            max_per_user,
+        } => configure::configure(deps, info, name, description, nft_address, max_per_user),
+        ExecuteMsg::SetReservationDeadline {
+            reservation_deadline,
+        } => refund::set_reservation_deadline(deps, info, reservation_deadline),
+        ExecuteMsg::SetTeamReserve { team_reserve } => {
+            configure::set_team_reserve(deps, info, team_reserve)
+        }
+        ExecuteMsg::Airdrop { recipients } => mint::airdrop(deps, env, info, recipients),
+        ExecuteMsg::SettleMints { limit } => mint::settle_mints(deps, env, limit),
+        ExecuteMsg::CancelQueuedMint { queue_id } => {
+            refund::cancel_queued_mint(deps, env, info, queue_id)
+        }
+        ExecuteMsg::Pause {} => pause::pause(deps, info),
+        ExecuteMsg::Unpause {} => pause::unpause(deps, info),
+        ExecuteMsg::SetPauser { pauser } => pause::set_pauser(deps, info, pauser),
+        ExecuteMsg::ClaimRefund { token_id } => refund::claim_refund(deps, env, info, token_id),
+        ExecuteMsg::ClaimRebate {} => rebate::claim_rebate(deps, env, info),
+        ExecuteMsg::ConfigureBaseUri {
+            base_uri,
+            token_uri_suffix,
+            freeze,
+        } => configure::configure_base_uri(deps, info, base_uri, token_uri_suffix, freeze),
+        ExecuteMsg::CommitReveal { base_uri_hash } => {
+            reveal::commit_reveal(deps, info, base_uri_hash)
+        }
+        ExecuteMsg::Reveal { base_uri } => reveal::reveal(deps, info, base_uri),
     }
 }
 
@@ -137,19 +218,36 @@ where
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
-        QueryMsg::Config {} => result_to_binary(query::query_config(deps)),
+        QueryMsg::Config {} => result_to_binary(query::query_config(deps, env)),
         QueryMsg::State {} => result_to_binary(query::query_state(deps)),
         QueryMsg::MintStages { limit } => result_to_binary(query::query_mint_stages(deps, limit)),
         QueryMsg::MintStage { stage_id } => {
             result_to_binary(query::query_mint_stage(deps, stage_id))
         }
-        QueryMsg::IsWhitelisted { stage_id, address } => {
-            result_to_binary(query::is_whitelisted(deps, stage_id, address))
+        QueryMsg::CurrentPrice { stage_id } => {
+            result_to_binary(query::query_current_price(deps, env, stage_id))
         }
+        QueryMsg::IsWhitelisted {
+            stage_id,
+            address,
+            merkle_proof,
+        } => result_to_binary(query::is_whitelisted(deps, stage_id, address, merkle_proof)),
         QueryMsg::UnprocessedReservations { start_after, limit } => result_to_binary(
             query::query_unprocessed_reservations(deps, start_after, limit),
         ),
+        QueryMsg::UserMints { address } => result_to_binary(query::query_user_mints(deps, address)),
+        QueryMsg::PendingRebate { address } => {
+            result_to_binary(query::query_pending_rebate(deps, env, address))
+        }
+        QueryMsg::Reservation { token_id } => {
+            result_to_binary(query::query_reservation(deps, token_id))
+        }
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    migrate::migrate(deps, env, msg)
+}
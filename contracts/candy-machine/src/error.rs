@@ -16,21 +16,66 @@ pub enum ContractError {
     #[error(transparent)]
     CryptoVerify(#[from] VerificationError),
 
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("No ownership transfer proposed")]
+    NoPendingOwner {},
+
     #[error("Nft address is not defined")]
     NftAddressNotDefined {},
 
     #[error("Unknown mint stage")]
     UnknownMintStage {},
 
+    #[error("Mint stage {id:?} already exists")]
+    MintStageAlreadyExists { id: u8 },
+
+    #[error("Mint stage start must be before finish. (start: {start:?}, finish: {finish:?})")]
+    InvalidMintStageTime { start: u64, finish: u64 },
+
+    #[error("Mint stage overlaps with mint stage {id:?}")]
+    MintStageOverlap { id: u8 },
+
+    #[error("Mint stage {id:?} max per user must be greater than zero")]
+    InvalidMintStageMaxPerUser { id: u8 },
+
+    #[error("Mint stage {id:?} max supply must be greater than zero")]
+    InvalidMintStageMaxSupply { id: u8 },
+
+    #[error("Mint stage {id:?} price must be greater than zero")]
+    InvalidMintStagePrice { id: u8 },
+
+    #[error("Invalid dutch auction of mint stage {id:?}")]
+    InvalidDutchAuction { id: u8 },
+
+    #[error("Mint stage {id:?} merkle root must be a base64 encoded sha256 hash")]
+    InvalidMerkleRoot { id: u8 },
+
+    #[error("Mint stage {id:?} merkle root requires the whitelist to be enabled")]
+    MerkleRootWhitelistDisabled { id: u8 },
+
+    #[error("Mint stage {id:?} has auction purchases and cannot be removed")]
+    MintStageHasPurchases { id: u8 },
+
     #[error("No more nfts to mint")]
     NoMoreNftsToMint {},
 
+    #[error("Only {remaining:?} nfts left to mint")]
+    NotEnoughNftsToMint { remaining: u32 },
+
+    #[error("Quantity must be greater than zero")]
+    InvalidQuantity {},
+
     #[error("Mint amount per user exceeded")]
     MaximumMintAmountPerUserExceeded {},
 
+    #[error("Mint stage {id:?} is sold out")]
+    MintStageSoldOut { id: u8 },
+
     #[error("Mint not started. (time: {start:?})")]
     MintNotStarted { start: u64 },
 
@@ -40,6 +85,9 @@ pub enum ContractError {
     #[error("{address:?} is not whitelisted for mint")]
     NotAllowNonWhitelisted { address: String },
 
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof {},
+
     #[error("Zero amount not allowed")]
     NotAllowZeroAmount {},
 
@@ -49,12 +97,129 @@ pub enum ContractError {
     #[error("Invalid amount, expected amount: {amount:?}")]
     InvalidAmount { amount: Uint128 },
 
+    #[error("Native payment not accepted, pay with cw20 token {address:?}")]
+    NativePaymentNotAccepted { address: String },
+
+    #[error("Payment with cw20 token {address:?} not accepted")]
+    Cw20PaymentNotAccepted { address: String },
+
+    #[error("Minting is paused")]
+    Paused {},
+
+    #[error("Team reserve can be at most {max:?}")]
+    InvalidTeamReserve { max: u32 },
+
     #[error("Invalid collection kind")]
     InvalidCollectionKind {},
 
+    #[error("Collectible collections can only be reserved, use Reserve")]
+    ReserveRequired {},
+
+    #[error("Single collections can only be minted, use Mint")]
+    MintRequired {},
+
     #[error("Unknown reservation")]
     UnknownReservation {},
 
+    #[error("Reservation for token {token_id:?} was already processed")]
+    ReservationAlreadyProcessed { token_id: u32 },
+
+    #[error("Reservation refunds are disabled")]
+    RefundsDisabled {},
+
+    #[error("Reservation can be refunded after {deadline:?}")]
+    ReservationDeadlineNotReached { deadline: u64 },
+
+    #[error("No rebate to claim")]
+    NoRebateToClaim {},
+
     #[error("Invalid signature")]
     InvalidSignature {},
+
+    #[error("Invalid signature count, expected one signature per token: {expected:?}")]
+    InvalidSignatureCount { expected: u16 },
+
+    #[error("Public key expiry must be in the future. (time: {expires_at:?})")]
+    InvalidPublicKeyExpiry { expires_at: u64 },
+
+    #[error("Signature expired. (time: {expires_at:?})")]
+    SignatureExpired { expires_at: u64 },
+
+    #[error("Signature nonce {nonce:?} already used")]
+    NonceAlreadyUsed { nonce: u64 },
+
+    #[error("Token ids have already been assigned")]
+    ShuffleAlreadyStarted {},
+
+    #[error("Invalid shuffle seed hash, expected a base64 encoded sha256 hash")]
+    InvalidShuffleSeedHash {},
+
+    #[error("Shuffle seed was not committed")]
+    ShuffleSeedNotCommitted {},
+
+    #[error("Shuffle seed was not revealed")]
+    ShuffleSeedNotRevealed {},
+
+    #[error("Shuffle seed was already revealed")]
+    ShuffleSeedAlreadyRevealed {},
+
+    #[error("Shuffle seed does not match the committed hash")]
+    InvalidShuffleSeed {},
+
+    #[error("Shuffled sale closed with the seed reveal, reveal the seed only after the sale ends")]
+    ShuffledSaleClosed {},
+
+    #[error("Queued mints are settled once the drop is revealed")]
+    DropNotRevealed {},
+
+    #[error("No queued mints to settle")]
+    NoMintsToSettle {},
+
+    #[error("Queued mints have to be settled first")]
+    QueuedMintsNotSettled {},
+
+    #[error("Unknown queued mint")]
+    UnknownQueuedMint {},
+
+    #[error("Drop was already revealed, queued mints can only be settled")]
+    DropAlreadyRevealed {},
+
+    #[error("Tokens have already been minted")]
+    RevealAlreadyStarted {},
+
+    #[error("Invalid base uri hash, expected a base64 encoded sha256 hash")]
+    InvalidBaseUriHash {},
+
+    #[error("Base uri was not committed")]
+    RevealNotCommitted {},
+
+    #[error("Base uri was already revealed")]
+    AlreadyRevealed {},
+
+    #[error("Base uri does not match the committed hash")]
+    InvalidBaseUri {},
+
+    #[error("Base uri is frozen")]
+    BaseUriFrozen {},
+
+    #[error("No funds to withdraw")]
+    NoFundsToWithdraw {},
+
+    #[error("Insufficient {denom:?} balance, available amount: {available:?}")]
+    InsufficientFunds { denom: String, available: Uint128 },
+
+    #[error("Cannot migrate from different contract type: {previous_contract:?}")]
+    CannotMigrate { previous_contract: String },
+
+    #[error("Cannot migrate from newer version ({previous_version:?}) to older ({new_version:?})")]
+    CannotMigrateVersion {
+        previous_version: String,
+        new_version: String,
+    },
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
 }
@@ -1,9 +1,18 @@
-use crate::msg::{ConfigureMintStageMsg, MintStage};
-use cosmwasm_std::{attr, Addr, DepsMut, MessageInfo, Response, Uint128};
+use crate::msg::{ConfigureMintStageMsg, ConfigurePublicKeyMsg, DutchAuction, MintStage};
+use cosmwasm_std::{attr, Addr, Coin, DepsMut, Env, MessageInfo, Response};
+use cw20::Cw20Coin;
 
 use crate::error::ContractError;
+use crate::handlers::ownership::assert_owner;
+use crate::handlers::validation::{validate_mint_stage, validate_mint_stage_against};
+use crate::state::collection_kind::{CollectionKind, SecondaryPublicKey};
 use crate::state::config::{Config, CONFIG};
-use crate::state::stage::{load as load_mint_stage, store as store_mint_stage};
+use crate::state::rebate;
+use crate::state::stage::{
+    load as load_mint_stage, load_all as load_all_mint_stages, remove as remove_mint_stage_state,
+    store as store_mint_stage,
+};
+use crate::state::state::{State, STATE};
 use crate::state::user;
 
 pub fn configure(
@@ -12,12 +21,9 @@ pub fn configure(
     name: Option<String>,
     description: Option<String>,
     nft_address: Option<String>,
+    max_per_user: Option<u16>,
 ) -> Result<Response, ContractError> {
-    let mut config: Config = CONFIG.load(deps.storage)?;
-
-    if info.sender != config.owner {
-        return Err(ContractError::Unauthorized {});
-    }
+    let mut config: Config = assert_owner(deps.storage, &info.sender)?;
 
     if let Some(name) = name {
         config.name = name;
@@ -32,6 +38,10 @@ pub fn configure(
         config.nft_address = Some(validated_nft_address);
     }
 
+    if let Some(max_per_user) = max_per_user {
+        config.max_per_user = Some(max_per_user);
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(vec![
@@ -40,17 +50,142 @@ pub fn configure(
     ]))
 }
 
+pub fn configure_public_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ConfigurePublicKeyMsg,
+) -> Result<Response, ContractError> {
+    let mut config: Config = assert_owner(deps.storage, &info.sender)?;
+
+    match &mut config.collection_kind {
+        CollectionKind::Single { .. } => return Err(ContractError::InvalidCollectionKind {}),
+        CollectionKind::Collectible {
+            minter: _,
+            cover: _,
+            public_key,
+            key_type,
+            secondary_key,
+        } => match msg {
+            ConfigurePublicKeyMsg::Rotate {
+                public_key: new_public_key,
+                key_type: new_key_type,
+            } => {
+                base64::decode(&new_public_key)?;
+                *public_key = Some(new_public_key);
+                *key_type = new_key_type;
+            }
+            ConfigurePublicKeyMsg::AddSecondary {
+                public_key,
+                key_type,
+                expires_at,
+            } => {
+                if expires_at <= env.block.time.seconds() {
+                    return Err(ContractError::InvalidPublicKeyExpiry { expires_at });
+                }
+                base64::decode(&public_key)?;
+                *secondary_key = Some(SecondaryPublicKey {
+                    public_key,
+                    key_type,
+                    expires_at,
+                });
+            }
+            ConfigurePublicKeyMsg::Remove {} => {
+                *public_key = None;
+                *key_type = None;
+                *secondary_key = None;
+            }
+        },
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "configure_public_key"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
+
+pub fn configure_base_uri(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_base_uri: Option<String>,
+    new_token_uri_suffix: Option<String>,
+    freeze: Option<bool>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = assert_owner(deps.storage, &info.sender)?;
+
+    match &mut config.collection_kind {
+        CollectionKind::Collectible { .. } => return Err(ContractError::InvalidCollectionKind {}),
+        CollectionKind::Single {
+            base_uri,
+            token_uri_suffix,
+            base_uri_frozen,
+            ..
+        } => {
+            if *base_uri_frozen {
+                return Err(ContractError::BaseUriFrozen {});
+            }
+
+            if let Some(new_base_uri) = new_base_uri {
+                *base_uri = Some(new_base_uri);
+            }
+
+            if let Some(new_token_uri_suffix) = new_token_uri_suffix {
+                *token_uri_suffix = Some(new_token_uri_suffix);
+            }
+
+            *base_uri_frozen = freeze.unwrap_or(false);
+        }
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "configure_base_uri"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
+
+/// Holds back `team_reserve` tokens in total for airdrops, public stages
+/// cannot mint the part of it that was not airdropped yet.
+pub fn set_team_reserve(
+    deps: DepsMut,
+    info: MessageInfo,
+    team_reserve: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = assert_owner(deps.storage, &info.sender)?;
+    let state: State = STATE.load(deps.storage)?;
+
+    if let Some(team_reserve) = team_reserve {
+        let max = config.max_token_count - state.token_count + state.airdropped;
+        if team_reserve > max {
+            return Err(ContractError::InvalidTeamReserve { max });
+        }
+    }
+
+    config.team_reserve = team_reserve;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_team_reserve"),
+        attr("sender", info.sender.to_string()),
+        attr(
+            "team_reserve",
+            team_reserve
+                .map(|team_reserve| team_reserve.to_string())
+                .unwrap_or_default(),
+        ),
+    ]))
+}
+
 pub fn configure_mint_stage(
     deps: DepsMut,
     info: MessageInfo,
     stage_id: u8,
     msg: ConfigureMintStageMsg,
 ) -> Result<Response, ContractError> {
-    let config: Config = CONFIG.load(deps.storage)?;
-
-    if info.sender != config.owner {
-        return Err(ContractError::Unauthorized {});
-    }
+    assert_owner(deps.storage, &info.sender)?;
 
     match msg {
         ConfigureMintStageMsg::Config {
@@ -58,8 +193,10 @@ pub fn configure_mint_stage(
             start,
             finish,
             price,
+            cw20_price,
             max_per_user,
             whitelist_enabled,
+            max_supply,
         } => update_mint_stage(
             deps,
             info,
@@ -68,13 +205,21 @@ pub fn configure_mint_stage(
             start,
             finish,
             price,
+            cw20_price,
             max_per_user,
             whitelist_enabled,
+            max_supply,
         ),
         ConfigureMintStageMsg::Whitelist {
             whitelist,
             candidates,
         } => update_whitelist(deps, info, stage_id, whitelist, candidates),
+        ConfigureMintStageMsg::MerkleRoot { merkle_root } => {
+            update_merkle_root(deps, info, stage_id, merkle_root)
+        }
+        ConfigureMintStageMsg::DutchAuction { dutch_auction } => {
+            update_dutch_auction(deps, info, stage_id, dutch_auction)
+        }
     }
 }
 
@@ -104,6 +249,50 @@ pub fn update_whitelist(
     ]))
 }
 
+pub fn update_merkle_root(
+    deps: DepsMut,
+    info: MessageInfo,
+    stage_id: u8,
+    merkle_root: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut stage: MintStage =
+        load_mint_stage(deps.storage, stage_id).ok_or(ContractError::UnknownMintStage {})?;
+
+    // setting a root restricts the stage to the merkle whitelist
+    if merkle_root.is_some() {
+        stage.whitelist_enabled = true;
+    }
+    stage.merkle_root = merkle_root;
+    validate_mint_stage(deps.api, &stage)?;
+
+    store_mint_stage(deps.storage, stage_id, &stage)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_merkle_root"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
+
+pub fn update_dutch_auction(
+    deps: DepsMut,
+    info: MessageInfo,
+    stage_id: u8,
+    dutch_auction: Option<DutchAuction>,
+) -> Result<Response, ContractError> {
+    let mut stage: MintStage =
+        load_mint_stage(deps.storage, stage_id).ok_or(ContractError::UnknownMintStage {})?;
+
+    stage.dutch_auction = dutch_auction;
+    validate_mint_stage(deps.api, &stage)?;
+
+    store_mint_stage(deps.storage, stage_id, &stage)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_dutch_auction"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
+
 pub fn update_mint_stage(
     deps: DepsMut,
     info: MessageInfo,
@@ -111,9 +300,11 @@ pub fn update_mint_stage(
     name: Option<String>,
     start: Option<u64>,
     finish: Option<u64>,
-    price: Option<Uint128>,
+    price: Option<Coin>,
+    cw20_price: Option<Cw20Coin>,
     max_per_user: Option<u16>,
     whitelist_enabled: Option<bool>,
+    max_supply: Option<u32>,
 ) -> Result<Response, ContractError> {
     let mut stage: MintStage =
         load_mint_stage(deps.storage, stage_id).ok_or(ContractError::UnknownMintStage {})?;
@@ -134,6 +325,10 @@ pub fn update_mint_stage(
         stage.price = Some(price);
     }
 
+    if let Some(cw20_price) = cw20_price {
+        stage.cw20_price = Some(cw20_price);
+    }
+
     if let Some(max_per_user) = max_per_user {
         stage.max_per_user = Some(max_per_user);
     }
@@ -142,6 +337,19 @@ pub fn update_mint_stage(
         stage.whitelist_enabled = whitelist_enabled;
     }
 
+    if let Some(max_supply) = max_supply {
+        stage.max_supply = Some(max_supply);
+    }
+
+    // overlaps are only checked when the time frame of the stage changes
+    match start.is_some() || finish.is_some() {
+        true => {
+            let stages = load_all_mint_stages(deps.storage)?;
+            validate_mint_stage_against(deps.api, &stage, &stages)?;
+        }
+        false => validate_mint_stage(deps.api, &stage)?,
+    }
+
     store_mint_stage(deps.storage, stage_id, &stage)?;
 
     Ok(Response::new().add_attributes(vec![
@@ -149,3 +357,49 @@ pub fn update_mint_stage(
         attr("sender", info.sender.to_string()),
     ]))
 }
+
+pub fn add_mint_stage(
+    deps: DepsMut,
+    info: MessageInfo,
+    stage: MintStage,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    if load_mint_stage(deps.storage, stage.id).is_some() {
+        return Err(ContractError::MintStageAlreadyExists { id: stage.id });
+    }
+
+    let stages: Vec<MintStage> = load_all_mint_stages(deps.storage)?;
+    validate_mint_stage_against(deps.api, &stage, &stages)?;
+
+    store_mint_stage(deps.storage, stage.id, &stage)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_mint_stage"),
+        attr("sender", info.sender.to_string()),
+        attr("stage_id", stage.id.to_string()),
+    ]))
+}
+
+pub fn remove_mint_stage(
+    deps: DepsMut,
+    info: MessageInfo,
+    stage_id: u8,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    load_mint_stage(deps.storage, stage_id).ok_or(ContractError::UnknownMintStage {})?;
+    // rebates are looked up through the stage, its buyers would lose them
+    if rebate::load_last_price(deps.storage, stage_id).is_some() {
+        return Err(ContractError::MintStageHasPurchases { id: stage_id });
+    }
+    remove_mint_stage_state(deps.storage, stage_id);
+    // a stage added later with the same id starts without mints or whitelist
+    user::remove_stage(deps.storage, stage_id)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_mint_stage"),
+        attr("sender", info.sender.to_string()),
+        attr("stage_id", stage_id.to_string()),
+    ]))
+}
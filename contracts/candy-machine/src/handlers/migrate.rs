@@ -0,0 +1,105 @@
+use cosmwasm_std::{coin, DepsMut, Env, Order, Response, StdResult, Storage};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::U32Key;
+use semver::Version;
+
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::ContractError;
+use crate::msg::{MigrateMsg, MintStage, ReservationStatus};
+use crate::state::legacy::{
+    MintStageV0_1_0, ReservationV0_1_0, DENOM_V0_1_0, RESERVATION_V0_1_0, STAGE_V0_1_0,
+};
+use crate::state::reservation::{self, Reservation, UNPROCESSED};
+use crate::state::stage::store as store_mint_stage;
+
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrate {
+            previous_contract: stored.contract,
+        });
+    }
+
+    let previous_version: Version = stored.version.parse()?;
+    let new_version: Version = CONTRACT_VERSION.parse()?;
+    if previous_version > new_version {
+        return Err(ContractError::CannotMigrateVersion {
+            previous_version: stored.version,
+            new_version: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // run every migration newer than the stored version in order
+    if previous_version < Version::new(0, 2, 0) {
+        migrate_from_0_1_0(deps.storage, &env)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("previous_version", stored.version)
+        .add_attribute("new_version", CONTRACT_VERSION))
+}
+
+fn migrate_from_0_1_0(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    migrate_stages_from_0_1_0(storage)?;
+    migrate_reservations_from_0_1_0(storage, env)
+}
+
+fn migrate_stages_from_0_1_0(storage: &mut dyn Storage) -> StdResult<()> {
+    let legacy_stages: Vec<MintStageV0_1_0> = STAGE_V0_1_0
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, v)| v))
+        .collect::<StdResult<_>>()?;
+
+    legacy_stages.into_iter().try_for_each(|legacy| {
+        store_mint_stage(
+            storage,
+            legacy.id,
+            &MintStage {
+                id: legacy.id,
+                name: legacy.name,
+                start: legacy.start,
+                finish: legacy.finish,
+                max_per_user: legacy.max_per_user,
+                price: legacy.price.map(|amount| coin(amount.u128(), DENOM_V0_1_0)),
+                cw20_price: None,
+                whitelist_enabled: legacy.whitelist_enabled,
+                merkle_root: None,
+                max_supply: None,
+                dutch_auction: None,
+            },
+        )
+    })
+}
+
+// reservations still waiting for the minter are pending, all others were minted.
+// The paid amount was not recorded, the deadline starts with the migration.
+fn migrate_reservations_from_0_1_0(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    let legacy_reservations: Vec<ReservationV0_1_0> = RESERVATION_V0_1_0
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, v)| v))
+        .collect::<StdResult<_>>()?;
+
+    legacy_reservations.into_iter().try_for_each(|legacy| {
+        let status = match UNPROCESSED.has(storage, U32Key::from(legacy.token_id)) {
+            true => ReservationStatus::Pending,
+            false => ReservationStatus::Minted,
+        };
+
+        reservation::store(
+            storage,
+            legacy.token_id,
+            &Reservation {
+                token_id: legacy.token_id,
+                user_address: legacy.user_address,
+                status,
+                stage_id: None,
+                airdropped: false,
+                paid: None,
+                reserved_at: env.block.time.seconds(),
+            },
+        )
+    })
+}
@@ -1,18 +1,37 @@
-use crate::msg::MintStage;
-use base64;
+use crate::msg::{
+    Cw20HookMsg, MerkleProof, MintStage, ReservationPayment, ReservationSignature,
+    ReservationStatus,
+};
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg,
+    from_binary, to_binary, Addr, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult,
+    Storage, Uint128, WasmMsg,
 };
+use cw20::{Cw20Coin, Cw20ReceiveMsg};
 use cw721_metadata_onchain::{ExecuteMsg as Cw721ExecuteMsg, Metadata, MintMsg};
-use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
-use crate::state::collection_kind::CollectionKind;
+use crate::handlers::ownership::assert_owner;
+use crate::handlers::pause::assert_not_paused;
+use crate::handlers::pricing::current_price;
+use crate::handlers::rebate::record_purchase;
+use crate::handlers::shuffle::draw_token_ids;
+use crate::handlers::signature::verify_reservation_signatures;
+use crate::handlers::whitelist::check_whitelisted;
+use crate::state::collection_kind::{active_public_keys, CollectionKind};
 use crate::state::config::{Config, CONFIG};
+use crate::state::queue::{self, QueuedMint};
 use crate::state::reservation;
-use crate::state::stage::load as load_mint_stage;
+use crate::state::reveal::{Reveal, REVEAL};
+use crate::state::shuffle::{Shuffle, SHUFFLE};
+use crate::state::stage::{self, load as load_mint_stage};
 use crate::state::state::{State, STATE};
-use crate::state::user::{is_whitelisted, load as load_user, store as store_user};
+use crate::state::user::{
+    load as load_user, load_stage as load_user_stage, store as store_user,
+    store_stage as store_user_stage,
+};
+
+const DEFAULT_SETTLE_LIMIT: u32 = 10;
+const MAX_SETTLE_LIMIT: u32 = 30;
 
 pub fn minter_mint(
     deps: DepsMut,
@@ -20,24 +39,87 @@ pub fn minter_mint(
     token_id: u32,
     metadata: Metadata,
 ) -> Result<Response, ContractError> {
-    let config: Config = CONFIG.load(deps.storage)?;
+    let config: Config = assert_minter(deps.storage, &info.sender)?;
+    assert_not_paused(deps.storage)?;
+
+    let (owner, mint_msg) = process_reservation(deps.storage, &config, token_id, metadata)?;
+
+    let resp = Response::default()
+        .add_attribute("action", "minter_mint")
+        .add_attribute("owner", owner.to_string())
+        .add_message(mint_msg);
+
+    Ok(resp)
+}
+
+pub fn minter_mint_batch(
+    deps: DepsMut,
+    info: MessageInfo,
+    items: Vec<(u32, Metadata)>,
+) -> Result<Response, ContractError> {
+    let config: Config = assert_minter(deps.storage, &info.sender)?;
+    assert_not_paused(deps.storage)?;
+
+    if items.is_empty() {
+        return Err(ContractError::InvalidQuantity {});
+    }
+
+    let mut token_ids: Vec<String> = Vec::with_capacity(items.len());
+    let mut mint_msgs: Vec<CosmosMsg> = Vec::with_capacity(items.len());
+    for (token_id, metadata) in items {
+        let (_, mint_msg) = process_reservation(deps.storage, &config, token_id, metadata)?;
+        token_ids.push(token_id.to_string());
+        mint_msgs.push(mint_msg);
+    }
+
+    let resp = Response::default()
+        .add_attribute("action", "minter_mint_batch")
+        .add_attribute("token_ids", token_ids.join(","))
+        .add_messages(mint_msgs);
+
+    Ok(resp)
+}
+
+fn assert_minter(storage: &dyn Storage, sender: &Addr) -> Result<Config, ContractError> {
+    let config: Config = CONFIG.load(storage)?;
 
-    let minter = match config.collection_kind {
-        CollectionKind::Single { image: _ } => Err(ContractError::InvalidCollectionKind {}),
+    let minter = match &config.collection_kind {
+        CollectionKind::Single { .. } => Err(ContractError::InvalidCollectionKind {}),
         CollectionKind::Collectible {
             public_key: _,
             cover: _,
+            key_type: _,
+            secondary_key: _,
             minter,
         } => Ok(minter),
     }?;
 
-    if info.sender != minter {
+    if sender != minter {
         return Err(ContractError::Unauthorized {});
     }
 
-    let user_reservation =
-        reservation::load(deps.storage, token_id).ok_or(ContractError::UnknownReservation {})?;
-    reservation::remove_unprocessed(deps.storage, token_id);
+    Ok(config)
+}
+
+// marks a pending reservation as minted and builds its cw721 mint message
+fn process_reservation(
+    storage: &mut dyn Storage,
+    config: &Config,
+    token_id: u32,
+    metadata: Metadata,
+) -> Result<(Addr, CosmosMsg), ContractError> {
+    let mut user_reservation =
+        reservation::load(storage, token_id).ok_or(ContractError::UnknownReservation {})?;
+    if user_reservation.status != ReservationStatus::Pending {
+        return Err(ContractError::ReservationAlreadyProcessed { token_id });
+    }
+
+    user_reservation.status = ReservationStatus::Minted;
+    reservation::store(storage, token_id, &user_reservation)?;
+    reservation::remove_unprocessed(storage, token_id);
+    if let Some(paid) = &user_reservation.paid {
+        reservation::release_payment(storage, paid)?;
+    }
 
     let mint_msg = MintMsg {
         token_id: token_id.to_string(),
@@ -46,16 +128,32 @@ pub fn minter_mint(
         extension: Some(metadata),
     };
 
-    let resp = Response::default()
-        .add_attribute("action", "minter_mint")
-        .add_attribute("owner", user_reservation.user_address.to_string())
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.nft_address.unwrap().to_string(),
-            msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg))?,
-            funds: vec![],
-        })]);
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.nft_address.clone().unwrap().to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg))?,
+        funds: vec![],
+    });
 
-    Ok(resp)
+    Ok((user_reservation.user_address, msg))
+}
+
+enum Payment {
+    Native(Vec<Coin>),
+    Cw20 { token: Addr, amount: Uint128 },
+}
+
+#[derive(PartialEq)]
+enum MintKind {
+    Mint,
+    Reserve,
+}
+
+struct MintRequest {
+    kind: MintKind,
+    stage_id: u8,
+    quantity: u16,
+    signatures: Option<Vec<ReservationSignature>>,
+    merkle_proof: Option<MerkleProof>,
 }
 
 pub fn mint(
@@ -63,10 +161,134 @@ pub fn mint(
     env: Env,
     info: MessageInfo,
     stage_id: u8,
-    signature: Option<String>,
+    quantity: Option<u16>,
+    signatures: Option<Vec<ReservationSignature>>,
+    merkle_proof: Option<MerkleProof>,
 ) -> Result<Response, ContractError> {
+    mint_for(
+        deps,
+        env,
+        info.sender,
+        Payment::Native(info.funds),
+        MintRequest {
+            kind: MintKind::Mint,
+            stage_id,
+            quantity: quantity.unwrap_or(1),
+            signatures,
+            merkle_proof,
+        },
+    )
+}
+
+pub fn reserve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage_id: u8,
+    quantity: Option<u16>,
+    signatures: Option<Vec<ReservationSignature>>,
+    merkle_proof: Option<MerkleProof>,
+) -> Result<Response, ContractError> {
+    mint_for(
+        deps,
+        env,
+        info.sender,
+        Payment::Native(info.funds),
+        MintRequest {
+            kind: MintKind::Reserve,
+            stage_id,
+            quantity: quantity.unwrap_or(1),
+            signatures,
+            merkle_proof,
+        },
+    )
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+    let payment = Payment::Cw20 {
+        token: info.sender,
+        amount: cw20_msg.amount,
+    };
+
+    let request = match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Mint {
+            stage_id,
+            quantity,
+            signatures,
+            merkle_proof,
+        } => MintRequest {
+            kind: MintKind::Mint,
+            stage_id,
+            quantity: quantity.unwrap_or(1),
+            signatures,
+            merkle_proof,
+        },
+        Cw20HookMsg::Reserve {
+            stage_id,
+            quantity,
+            signatures,
+            merkle_proof,
+        } => MintRequest {
+            kind: MintKind::Reserve,
+            stage_id,
+            quantity: quantity.unwrap_or(1),
+            signatures,
+            merkle_proof,
+        },
+    };
+
+    mint_for(deps, env, sender, payment, request)
+}
+
+fn mint_for(
+    mut deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    payment: Payment,
+    request: MintRequest,
+) -> Result<Response, ContractError> {
+    let MintRequest {
+        kind,
+        stage_id,
+        quantity,
+        signatures,
+        merkle_proof,
+    } = request;
+
+    if quantity == 0 {
+        return Err(ContractError::InvalidQuantity {});
+    }
+
+    assert_not_paused(deps.storage)?;
+
     let config: Config = CONFIG.load(deps.storage)?;
 
+    // single collections are minted right away, collectibles are reserved for the minter
+    match (&kind, &config.collection_kind) {
+        (MintKind::Mint, CollectionKind::Collectible { .. }) => {
+            return Err(ContractError::ReserveRequired {})
+        }
+        (MintKind::Reserve, CollectionKind::Single { .. }) => {
+            return Err(ContractError::MintRequired {})
+        }
+        _ => (),
+    }
+
+    // ids drawn after the shuffle seed is revealed could be computed in advance,
+    // so a shuffled sale closes with the reveal
+    if let Some(Shuffle {
+        entropy: Some(_), ..
+    }) = SHUFFLE.may_load(deps.storage)?
+    {
+        return Err(ContractError::ShuffledSaleClosed {});
+    }
+
     let mint_stage: MintStage =
         load_mint_stage(deps.storage, stage_id).ok_or(ContractError::UnknownMintStage {})?;
 
@@ -92,104 +314,458 @@ pub fn mint(
 
     // check if there are nfts available to mint
     let mut state: State = STATE.load(deps.storage)?;
-    if state.token_count >= config.max_token_count {
+    let remaining = public_remaining(&config, &state);
+    if remaining == 0 {
         return Err(ContractError::NoMoreNftsToMint {});
     }
 
-    // check price
-    if let Some(price) = mint_stage.price {
-        let amount = info
-            .funds
-            .iter()
-            .find(|c| c.denom == "uusd".to_string())
-            .map(|c| c.amount)
-            .unwrap_or_else(Uint128::zero);
-        if amount.is_zero() {
-            return Err(ContractError::NotAllowZeroAmount {});
-        }
-        if info.funds.len() > 1 {
-            return Err(ContractError::NotAllowOtherDenoms {
-                denom: "uusd".to_string(),
-            });
+    if u32::from(quantity) > remaining {
+        return Err(ContractError::NotEnoughNftsToMint { remaining });
+    }
+
+    // check if the stage has nfts left
+    let stage_minted = stage::load_minted(deps.storage, stage_id);
+    if let Some(max_supply) = mint_stage.max_supply {
+        if stage_minted >= max_supply {
+            return Err(ContractError::MintStageSoldOut { id: stage_id });
         }
-        if price != amount {
-            return Err(ContractError::InvalidAmount { amount: price });
+
+        let remaining = max_supply - stage_minted;
+        if u32::from(quantity) > remaining {
+            return Err(ContractError::NotEnoughNftsToMint { remaining });
         }
     }
 
+    // check price
+    let (price, cw20_price) = current_price(&mint_stage, now);
+    let paid = check_payment(price.as_ref(), cw20_price.as_ref(), payment, quantity)?;
+
     // check if user is allowed to mint
-    let sender = &deps.api.addr_canonicalize(info.sender.as_str())?;
+    let sender = &deps.api.addr_canonicalize(owner.as_str())?;
     let user_minted_amount = load_user(deps.storage, sender);
+    let user_stage_minted_amount = load_user_stage(deps.storage, stage_id, sender);
 
-    if mint_stage.whitelist_enabled && !is_whitelisted(deps.storage, stage_id, sender) {
-        return Err(ContractError::NotAllowNonWhitelisted {
-            address: info.sender.to_string(),
-        });
-    }
+    let allowance = match mint_stage.whitelist_enabled {
+        true => check_whitelisted(deps.as_ref(), &mint_stage, &owner, merkle_proof.as_ref())?,
+        false => None,
+    };
+
+    let user_minted_amount = user_minted_amount
+        .checked_add(quantity)
+        .ok_or(ContractError::MaximumMintAmountPerUserExceeded {})?;
+    let user_stage_minted_amount = user_stage_minted_amount
+        .checked_add(quantity)
+        .ok_or(ContractError::MaximumMintAmountPerUserExceeded {})?;
 
     if let Some(max_per_user) = mint_stage.max_per_user {
-        if user_minted_amount >= max_per_user {
+        if user_stage_minted_amount > max_per_user {
+            return Err(ContractError::MaximumMintAmountPerUserExceeded {});
+        }
+    }
+
+    if let Some(allowance) = allowance {
+        if user_stage_minted_amount > allowance {
+            return Err(ContractError::MaximumMintAmountPerUserExceeded {});
+        }
+    }
+
+    if let Some(max_per_user) = config.max_per_user {
+        if user_minted_amount > max_per_user {
             return Err(ContractError::MaximumMintAmountPerUserExceeded {});
         }
     }
 
     // update user state and global state
-    store_user(deps.storage, sender, user_minted_amount + 1)?;
-    state.token_count += 1;
-    STATE.save(deps.storage, &state)?;
+    store_user(deps.storage, sender, user_minted_amount)?;
+    store_user_stage(deps.storage, stage_id, sender, user_stage_minted_amount)?;
+    stage::store_minted(deps.storage, stage_id, stage_minted + u32::from(quantity))?;
+    if let (Some(_), Some(price)) = (&mint_stage.dutch_auction, &paid) {
+        record_purchase(deps.storage, &mint_stage, &owner, price, quantity)?;
+    }
+
+    if let CollectionKind::Collectible { .. } = &config.collection_kind {
+        let public_keys = active_public_keys(&config.collection_kind, now);
+        if !public_keys.is_empty() {
+            let signatures = signatures.ok_or(ContractError::InvalidSignature {})?;
+            if signatures.len() != usize::from(quantity) {
+                return Err(ContractError::InvalidSignatureCount { expected: quantity });
+            }
+
+            verify_reservation_signatures(
+                deps.branch(),
+                &env,
+                &owner,
+                stage_id,
+                &public_keys,
+                &signatures,
+            )?;
+        }
+    }
+
+    if is_hidden(deps.storage)? {
+        let queue_id = queue_mint(
+            deps.storage,
+            &mut state,
+            QueuedMint {
+                owner: owner.clone(),
+                quantity: quantity.into(),
+                stage_id: Some(stage_id),
+                paid,
+                queued_at: now,
+            },
+        )?;
+        STATE.save(deps.storage, &state)?;
+
+        return Ok(Response::new()
+            .add_attribute("action", "queue_mint")
+            .add_attribute("owner", owner.to_string())
+            .add_attribute("stage_id", stage_id.to_string())
+            .add_attribute("queue_id", queue_id.to_string()));
+    }
 
     // mint
-    match config.collection_kind.clone() {
-        CollectionKind::Single { image } => {
-            mint_single(info.sender.to_string(), state.token_count, config, image)
+    let token_ids = assign_token_ids(deps.storage, &config, &state, &owner, u32::from(quantity))?;
+    state.token_count += u32::from(quantity);
+    STATE.save(deps.storage, &state)?;
+
+    let token_ids_attr = token_ids
+        .iter()
+        .map(|token_id| token_id.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let res = mint_tokens(deps, config, owner, token_ids, Some(stage_id), paid, now)?;
+
+    Ok(res
+        .add_attribute("stage_id", stage_id.to_string())
+        .add_attribute("token_ids", token_ids_attr))
+}
+
+/// Owner-only mint outside of the mint stages, for the team or giveaways. Airdrops
+/// use up the team reserve first and count against the max token count.
+pub fn airdrop(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipients: Vec<(String, u32)>,
+) -> Result<Response, ContractError> {
+    let config: Config = assert_owner(deps.storage, &info.sender)?;
+
+    if recipients.is_empty() || recipients.iter().any(|(_, quantity)| *quantity == 0) {
+        return Err(ContractError::InvalidQuantity {});
+    }
+
+    config
+        .nft_address
+        .clone()
+        .ok_or(ContractError::NftAddressNotDefined {})?;
+
+    // airdrops can mint the team reserve on top of the public supply
+    let mut state: State = STATE.load(deps.storage)?;
+    let remaining = config.max_token_count - state.token_count;
+    let quantity = recipients
+        .iter()
+        .try_fold(0u32, |total, (_, quantity)| total.checked_add(*quantity))
+        .ok_or(ContractError::NotEnoughNftsToMint { remaining })?;
+    if quantity > remaining {
+        return Err(ContractError::NotEnoughNftsToMint { remaining });
+    }
+
+    // with the seed revealed the next draws are known, airdrops could take the
+    // ids the queued mints are about to draw
+    let hidden = is_hidden(deps.storage)?;
+    if !hidden && state.queued > 0 && SHUFFLE.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::QueuedMintsNotSettled {});
+    }
+
+    let now = env.block.time.seconds();
+    let mut resp = Response::new().add_attribute("action", "airdrop");
+    for (recipient, quantity) in recipients {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        state.airdropped += quantity;
+        if hidden {
+            let queue_id = queue_mint(
+                deps.storage,
+                &mut state,
+                QueuedMint {
+                    owner: recipient.clone(),
+                    quantity,
+                    stage_id: None,
+                    paid: None,
+                    queued_at: now,
+                },
+            )?;
+            resp = resp
+                .add_attribute("recipient", recipient.to_string())
+                .add_attribute("queue_id", queue_id.to_string());
+            continue;
         }
-        CollectionKind::Collectible {
-            minter: _,
-            cover: _,
-            public_key,
-        } => mint_collectible(
-            deps,
-            info.sender.clone(),
-            state.token_count,
-            public_key,
-            signature,
-        ),
+
+        let token_ids = assign_token_ids(deps.storage, &config, &state, &recipient, quantity)?;
+        state.token_count += quantity;
+
+        let token_ids_attr = token_ids
+            .iter()
+            .map(|token_id| token_id.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let res = mint_tokens(
+            deps.branch(),
+            config.clone(),
+            recipient.clone(),
+            token_ids,
+            None,
+            None,
+            now,
+        )?;
+        resp = resp
+            .add_attribute("recipient", recipient.to_string())
+            .add_attribute("token_ids", token_ids_attr)
+            .add_submessages(res.messages);
     }
+    STATE.save(deps.storage, &state)?;
+
+    Ok(resp)
 }
 
-fn mint_collectible(
+/// Assigns token ids to the queued mints of a revealed drop, oldest first.
+/// Anyone can settle, the ids only depend on the revealed seed.
+pub fn settle_mints(
+    mut deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if is_hidden(deps.storage)? {
+        return Err(ContractError::DropNotRevealed {});
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_SETTLE_LIMIT).min(MAX_SETTLE_LIMIT) as usize;
+    let mints = queue::take(deps.storage, limit)?;
+    if mints.is_empty() {
+        return Err(ContractError::NoMintsToSettle {});
+    }
+
+    let mut state: State = STATE.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let mut resp = Response::new().add_attribute("action", "settle_mints");
+    for mint in mints {
+        let token_ids =
+            assign_token_ids(deps.storage, &config, &state, &mint.owner, mint.quantity)?;
+        state.queued -= mint.quantity;
+
+        let token_ids_attr = token_ids
+            .iter()
+            .map(|token_id| token_id.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let res = mint_tokens(
+            deps.branch(),
+            config.clone(),
+            mint.owner.clone(),
+            token_ids,
+            mint.stage_id,
+            mint.paid,
+            now,
+        )?;
+        resp = resp
+            .add_attribute("owner", mint.owner.to_string())
+            .add_attribute("token_ids", token_ids_attr)
+            .add_submessages(res.messages);
+    }
+    STATE.save(deps.storage, &state)?;
+
+    Ok(resp)
+}
+
+// a drop stays hidden while the shuffle seed or the base uri is committed but
+// not revealed yet, minted tokens could not be updated afterwards
+pub fn is_hidden(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(matches!(
+        SHUFFLE.may_load(storage)?,
+        Some(Shuffle { entropy: None, .. })
+    ) || matches!(
+        REVEAL.may_load(storage)?,
+        Some(Reveal { base_uri: None, .. })
+    ))
+}
+
+// the tokens count against the supply right away, ids are assigned on settlement
+fn queue_mint(storage: &mut dyn Storage, state: &mut State, mint: QueuedMint) -> StdResult<u32> {
+    let queue_id = queue::push(storage, &mint)?;
+    state.token_count += mint.quantity;
+    state.queued += mint.quantity;
+
+    Ok(queue_id)
+}
+
+/// Supply left to the mint stages, the part of the team reserve that was not
+/// airdropped yet is held back.
+pub fn public_remaining(config: &Config, state: &State) -> u32 {
+    let reserved = config
+        .team_reserve
+        .unwrap_or_default()
+        .saturating_sub(state.airdropped);
+
+    config
+        .max_token_count
+        .saturating_sub(state.token_count)
+        .saturating_sub(reserved)
+}
+
+// single collections are minted right away, collectibles are reserved for the owner
+fn mint_tokens(
     deps: DepsMut,
+    config: Config,
     owner: Addr,
-    token_count: u32,
-    public_key: Option<String>,
-    signature: Option<String>,
+    token_ids: Vec<u32>,
+    stage_id: Option<u8>,
+    paid: Option<ReservationPayment>,
+    now: u64,
 ) -> Result<Response, ContractError> {
-    if let Some(public_key_str) = public_key {
-        let hash = Sha256::digest(owner.as_bytes());
+    match config.collection_kind.clone() {
+        CollectionKind::Single {
+            image,
+            base_uri,
+            token_uri_suffix,
+            onchain_metadata,
+            ..
+        } => {
+            // tokens of a revealed drop point to the revealed metadata
+            let base_uri = match REVEAL.may_load(deps.storage)? {
+                Some(reveal) => reveal.base_uri,
+                None => base_uri,
+            };
+            mint_single(
+                owner.to_string(),
+                token_ids,
+                config,
+                image,
+                base_uri,
+                token_uri_suffix,
+                onchain_metadata.unwrap_or(true),
+            )
+        }
+        CollectionKind::Collectible { .. } => {
+            mint_collectible(deps, owner, token_ids, stage_id, paid, now)
+        }
+    }
+}
+
+// released token ids are handed out first, new ids are drawn from the ones never assigned
+fn assign_token_ids(
+    storage: &mut dyn Storage,
+    config: &Config,
+    state: &State,
+    recipient: &Addr,
+    quantity: u32,
+) -> Result<Vec<u32>, ContractError> {
+    // queued tokens are counted but have no id yet
+    let assigned = state.token_count - state.queued + reservation::released_count(storage);
+
+    let mut token_ids = reservation::take_released(storage, quantity as usize);
+    let missing = quantity - token_ids.len() as u32;
+    token_ids.extend(draw_token_ids(
+        storage,
+        recipient,
+        config.max_token_count,
+        assigned,
+        missing,
+    )?);
+
+    Ok(token_ids)
+}
+
+/// Checks the payment for `quantity` tokens and returns the price paid per token.
+fn check_payment(
+    price: Option<&Coin>,
+    cw20_price: Option<&Cw20Coin>,
+    payment: Payment,
+    quantity: u16,
+) -> Result<Option<ReservationPayment>, ContractError> {
+    match payment {
+        Payment::Native(funds) => {
+            let price = match (price, cw20_price) {
+                (Some(price), _) => price,
+                (None, Some(cw20_price)) => {
+                    return Err(ContractError::NativePaymentNotAccepted {
+                        address: cw20_price.address.clone(),
+                    })
+                }
+                (None, None) => return Ok(None),
+            };
 
-        let public_key = base64::decode(&public_key_str)?;
+            let amount = funds
+                .iter()
+                .find(|c| c.denom == price.denom)
+                .map(|c| c.amount)
+                .unwrap_or_else(Uint128::zero);
+            if amount.is_zero() {
+                return Err(ContractError::NotAllowZeroAmount {});
+            }
+            if funds.len() > 1 {
+                return Err(ContractError::NotAllowOtherDenoms {
+                    denom: price.denom.clone(),
+                });
+            }
+            let total_price = price.amount.checked_mul(Uint128::from(quantity))?;
+            if total_price != amount {
+                return Err(ContractError::InvalidAmount {
+                    amount: total_price,
+                });
+            }
 
-        let signature_msg = signature.ok_or(ContractError::InvalidSignature {})?;
-        let signature = base64::decode(&signature_msg)?;
+            Ok(Some(ReservationPayment::Native(price.clone())))
+        }
+        Payment::Cw20 { token, amount } => {
+            let price = cw20_price
+                .filter(|price| price.address == token.as_str())
+                .ok_or(ContractError::Cw20PaymentNotAccepted {
+                    address: token.to_string(),
+                })?;
 
-        let result = deps
-            .api
-            .secp256k1_verify(&hash, &signature, public_key.as_ref())?;
+            let total_price = price.amount.checked_mul(Uint128::from(quantity))?;
+            if total_price != amount {
+                return Err(ContractError::InvalidAmount {
+                    amount: total_price,
+                });
+            }
 
-        if !result {
-            return Err(ContractError::InvalidSignature {});
+            Ok(Some(ReservationPayment::Cw20(price.clone())))
         }
     }
+}
 
-    reservation::store(
-        deps.storage,
-        token_count,
-        &reservation::Reservation {
-            user_address: owner.clone(),
-            token_id: token_count,
-        },
-    )?;
-    reservation::store_unprocessed(deps.storage, token_count, &owner)?;
+fn mint_collectible(
+    deps: DepsMut,
+    owner: Addr,
+    token_ids: Vec<u32>,
+    stage_id: Option<u8>,
+    paid: Option<ReservationPayment>,
+    reserved_at: u64,
+) -> Result<Response, ContractError> {
+    for token_id in token_ids {
+        reservation::store(
+            deps.storage,
+            token_id,
+            &reservation::Reservation {
+                user_address: owner.clone(),
+                token_id,
+                status: ReservationStatus::Pending,
+                stage_id,
+                // only airdrops are reserved outside of the mint stages
+                airdropped: stage_id.is_none(),
+                paid: paid.clone(),
+                reserved_at,
+            },
+        )?;
+        reservation::store_unprocessed(deps.storage, token_id, &owner)?;
+        if let Some(paid) = &paid {
+            reservation::hold_payment(deps.storage, paid)?;
+        }
+    }
 
     Ok(Response::default()
         .add_attribute("action", "reserve")
@@ -198,31 +774,58 @@ fn mint_collectible(
 
 fn mint_single(
     owner: String,
-    token_count: u32,
+    token_ids: Vec<u32>,
     config: Config,
     image: String,
+    base_uri: Option<String>,
+    token_uri_suffix: Option<String>,
+    onchain_metadata: bool,
 ) -> Result<Response, ContractError> {
-    let mint_msg = MintMsg {
-        token_id: token_count.to_string(),
-        owner: owner.clone(),
-        token_uri: None,
-        extension: Some(Metadata {
-            image: Some(image),
-            description: Some(config.description),
-            name: Some(format!("{} #{}", config.name, token_count)),
-            attributes: None,
-            ..Metadata::default()
-        }),
-    };
+    let nft_address = config.nft_address.clone().unwrap().to_string();
+
+    let mint_msgs = token_ids
+        .into_iter()
+        .map(|token_id| {
+            let token_uri = base_uri.as_ref().map(|base_uri| {
+                format!(
+                    "{}{}{}",
+                    base_uri,
+                    token_id,
+                    token_uri_suffix.as_deref().unwrap_or_default()
+                )
+            });
+            // on-chain metadata can only be left out when the token has a uri
+            let extension = if onchain_metadata || token_uri.is_none() {
+                Some(Metadata {
+                    image: Some(image.clone()),
+                    description: Some(config.description.clone()),
+                    name: Some(format!("{} #{}", config.name, token_id)),
+                    attributes: None,
+                    ..Metadata::default()
+                })
+            } else {
+                None
+            };
+
+            let mint_msg = MintMsg {
+                token_id: token_id.to_string(),
+                owner: owner.clone(),
+                token_uri,
+                extension,
+            };
+
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: nft_address.clone(),
+                msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg))?,
+                funds: vec![],
+            }))
+        })
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
 
     let resp = Response::default()
         .add_attribute("action", "mint")
         .add_attribute("owner", owner)
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.nft_address.unwrap().to_string(),
-            msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg))?,
-            funds: vec![],
-        })]);
+        .add_messages(mint_msgs);
 
     Ok(resp)
 }
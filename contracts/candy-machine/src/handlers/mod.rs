@@ -1,4 +1,15 @@
 pub mod configure;
+pub mod migrate;
 pub mod mint;
+pub mod ownership;
+pub mod pause;
+pub mod pricing;
 pub mod query;
+pub mod rebate;
+pub mod refund;
+pub mod reveal;
+pub mod shuffle;
+pub mod signature;
+pub mod validation;
+pub mod whitelist;
 pub mod withdraw;
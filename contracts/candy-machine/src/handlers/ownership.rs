@@ -0,0 +1,77 @@
+use cosmwasm_std::{attr, Addr, DepsMut, MessageInfo, Response, Storage};
+
+use crate::error::ContractError;
+use crate::state::config::{Config, CONFIG};
+
+/// Loads config and checks that the sender is the current owner.
+pub fn assert_owner(storage: &dyn Storage, sender: &Addr) -> Result<Config, ContractError> {
+    let config: Config = CONFIG.load(storage)?;
+
+    if *sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(config)
+}
+
+pub fn propose_new_owner(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: String,
+) -> Result<Response, ContractError> {
+    let mut config: Config = assert_owner(deps.storage, &info.sender)?;
+
+    let pending_owner: Addr = deps.api.addr_validate(&owner)?;
+    config.pending_owner = Some(pending_owner.clone());
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_new_owner"),
+        attr("sender", info.sender.to_string()),
+        attr("pending_owner", pending_owner.to_string()),
+    ]))
+}
+
+pub fn accept_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    let pending_owner = config
+        .pending_owner
+        .take()
+        .ok_or(ContractError::NoPendingOwner {})?;
+
+    if info.sender != pending_owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let previous_owner = config.owner;
+    config.owner = pending_owner;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "accept_ownership"),
+        attr("previous_owner", previous_owner.to_string()),
+        attr("owner", config.owner.to_string()),
+    ]))
+}
+
+pub fn cancel_ownership_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config: Config = assert_owner(deps.storage, &info.sender)?;
+
+    if config.pending_owner.is_none() {
+        return Err(ContractError::NoPendingOwner {});
+    }
+    config.pending_owner = None;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_ownership_proposal"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
@@ -0,0 +1,79 @@
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response, Storage};
+
+use crate::error::ContractError;
+use crate::handlers::ownership::assert_owner;
+use crate::state::config::{Config, CONFIG};
+use crate::state::state::{State, STATE};
+
+/// Fails while minting is paused.
+pub fn assert_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    let state: State = STATE.load(storage)?;
+
+    if state.paused {
+        return Err(ContractError::Paused {});
+    }
+
+    Ok(())
+}
+
+fn set_paused(deps: DepsMut, paused: bool) -> Result<(), ContractError> {
+    let mut state: State = STATE.load(deps.storage)?;
+    state.paused = paused;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(())
+}
+
+/// Pauses minting, allowed for the owner and the pauser.
+pub fn pause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner && Some(&info.sender) != config.pauser.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    set_paused(deps, true)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "pause"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
+
+/// Resumes minting, only the owner can unpause.
+pub fn unpause(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    set_paused(deps, false)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "unpause"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
+
+pub fn set_pauser(
+    deps: DepsMut,
+    info: MessageInfo,
+    pauser: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = assert_owner(deps.storage, &info.sender)?;
+
+    config.pauser = pauser
+        .map(|pauser| deps.api.addr_validate(&pauser))
+        .transpose()?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_pauser"),
+        attr("sender", info.sender.to_string()),
+        attr(
+            "pauser",
+            config
+                .pauser
+                .map(|pauser| pauser.to_string())
+                .unwrap_or_default(),
+        ),
+    ]))
+}
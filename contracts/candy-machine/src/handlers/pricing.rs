@@ -0,0 +1,34 @@
+use cosmwasm_std::{Coin, Uint128};
+use cw20::Cw20Coin;
+
+use crate::msg::MintStage;
+
+/// Returns the stage prices at `now`, lowered along the dutch auction when one is configured.
+pub fn current_price(stage: &MintStage, now: u64) -> (Option<Coin>, Option<Cw20Coin>) {
+    let mut price = stage.price.clone();
+    let mut cw20_price = stage.cw20_price.clone();
+
+    if let Some(dutch_auction) = &stage.dutch_auction {
+        // validated on store, auction stages always have a start and a finish
+        let start = stage.start.unwrap_or_default();
+        let finish = stage.finish.unwrap_or_default();
+        let elapsed = now.saturating_sub(start).min(finish - start);
+
+        // the price drops at the end of every step and reaches the floor at finish
+        let step = u64::from(dutch_auction.steps) * elapsed / (finish - start);
+        let decay = |amount: Uint128| {
+            amount
+                - (amount - dutch_auction.floor_price)
+                    .multiply_ratio(step, u64::from(dutch_auction.steps))
+        };
+
+        if let Some(price) = price.as_mut() {
+            price.amount = decay(price.amount);
+        }
+        if let Some(cw20_price) = cw20_price.as_mut() {
+            cw20_price.amount = decay(cw20_price.amount);
+        }
+    }
+
+    (price, cw20_price)
+}
@@ -1,16 +1,24 @@
 use crate::msg::{
-    ConfigResponse, IsWhitelistedResponse, MintStage, MintStagesResponse, StateResponse,
-    UnprocessedReservationsResponse,
+    ConfigResponse, CurrentPriceResponse, IsWhitelistedResponse, MerkleProof, MintStage,
+    MintStageResponse, MintStagesResponse, PendingRebateResponse, ReservationResponse,
+    RevealResponse, ShuffleResponse, StageMints, StateResponse, UnprocessedReservationsResponse,
+    UserMintsResponse,
 };
-use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cosmwasm_std::{Addr, Deps, Env, Order, StdResult};
 use cw_storage_plus::Bound;
 use std::convert::TryInto;
 
 use crate::error::ContractError;
+use crate::handlers::mint::public_remaining;
+use crate::handlers::pricing::current_price;
+use crate::handlers::rebate;
+use crate::handlers::whitelist::check_whitelisted;
 use crate::state::collection_kind;
 use crate::state::config::{Config, CONFIG};
-use crate::state::reservation::UNPROCESSED;
-use crate::state::stage::{load as load_mint_stage, STAGE};
+use crate::state::reservation::{self, UNPROCESSED};
+use crate::state::reveal::REVEAL;
+use crate::state::shuffle::SHUFFLE;
+use crate::state::stage::{self, load as load_mint_stage, STAGE};
 use crate::state::state::{State, STATE};
 use crate::state::user;
 
@@ -25,14 +33,33 @@ fn address_to_string(address: Option<Addr>) -> Option<String> {
     }
 }
 
-pub fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
+pub fn query_config(deps: Deps, env: Env) -> Result<ConfigResponse, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
+    let active_public_keys =
+        collection_kind::active_public_keys(&config.collection_kind, env.block.time.seconds());
     Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        pending_owner: address_to_string(config.pending_owner),
+        pauser: address_to_string(config.pauser),
         nft_address: address_to_string(config.nft_address),
         name: config.name,
         description: config.description,
         collection_kind: collection_kind::to_msg(config.collection_kind),
         max_token_count: config.max_token_count,
+        max_per_user: config.max_per_user,
+        reservation_deadline: config.reservation_deadline,
+        team_reserve: config.team_reserve,
+        active_public_keys,
+        shuffle: SHUFFLE
+            .may_load(deps.storage)?
+            .map(|shuffle| ShuffleResponse {
+                seed_hash: shuffle.seed_hash,
+                revealed: shuffle.entropy.is_some(),
+            }),
+        reveal: REVEAL.may_load(deps.storage)?.map(|reveal| RevealResponse {
+            base_uri_hash: reveal.base_uri_hash,
+            base_uri: reveal.base_uri,
+        }),
     })
 }
 
@@ -40,6 +67,9 @@ pub fn query_state(deps: Deps) -> Result<StateResponse, ContractError> {
     let state: State = STATE.load(deps.storage)?;
     Ok(StateResponse {
         token_count: state.token_count,
+        paused: state.paused,
+        airdropped: state.airdropped,
+        queued: state.queued,
     })
 }
 
@@ -49,10 +79,13 @@ pub fn query_mint_stages(
 ) -> Result<MintStagesResponse, ContractError> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
 
-    let mint_stages: StdResult<Vec<MintStage>> = STAGE
+    let config: Config = CONFIG.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
+
+    let mint_stages: StdResult<Vec<MintStageResponse>> = STAGE
         .range(deps.storage, None, None, Order::Ascending)
         .take(limit)
-        .map(|item| item.map(|(_, v)| v))
+        .map(|item| item.map(|(_, v)| mint_stage_response(deps, &config, &state, v)))
         .collect();
 
     Ok(MintStagesResponse {
@@ -60,20 +93,69 @@ pub fn query_mint_stages(
     })
 }
 
-pub fn query_mint_stage(deps: Deps, stage_id: u8) -> Result<MintStage, ContractError> {
+pub fn query_mint_stage(deps: Deps, stage_id: u8) -> Result<MintStageResponse, ContractError> {
     let mint_stage =
         load_mint_stage(deps.storage, stage_id).ok_or(ContractError::UnknownMintStage {})?;
+    let config: Config = CONFIG.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
 
-    Ok(mint_stage)
+    Ok(mint_stage_response(deps, &config, &state, mint_stage))
+}
+
+fn mint_stage_response(
+    deps: Deps,
+    config: &Config,
+    state: &State,
+    stage: MintStage,
+) -> MintStageResponse {
+    let minted = stage::load_minted(deps.storage, stage.id);
+    let remaining = public_remaining(config, state);
+    let remaining = match stage.max_supply {
+        Some(max_supply) => remaining.min(max_supply.saturating_sub(minted)),
+        None => remaining,
+    };
+
+    MintStageResponse {
+        stage,
+        minted,
+        remaining,
+    }
+}
+
+pub fn query_pending_rebate(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> Result<PendingRebateResponse, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let rebates = rebate::pending_rebates(deps.storage, env.block.time.seconds(), &address)?;
+
+    Ok(PendingRebateResponse { rebates })
+}
+
+pub fn query_current_price(
+    deps: Deps,
+    env: Env,
+    stage_id: u8,
+) -> Result<CurrentPriceResponse, ContractError> {
+    let mint_stage =
+        load_mint_stage(deps.storage, stage_id).ok_or(ContractError::UnknownMintStage {})?;
+    let (price, cw20_price) = current_price(&mint_stage, env.block.time.seconds());
+
+    Ok(CurrentPriceResponse { price, cw20_price })
 }
 
 pub fn is_whitelisted(
     deps: Deps,
     stage_id: u8,
     address: String,
+    merkle_proof: Option<MerkleProof>,
 ) -> Result<IsWhitelistedResponse, ContractError> {
-    let user_addr = deps.api.addr_canonicalize(address.as_str())?;
-    let whitelisted = user::is_whitelisted(deps.storage, stage_id, &user_addr);
+    let mint_stage =
+        load_mint_stage(deps.storage, stage_id).ok_or(ContractError::UnknownMintStage {})?;
+    let user_addr = deps.api.addr_validate(address.as_str())?;
+    let whitelisted =
+        check_whitelisted(deps, &mint_stage, &user_addr, merkle_proof.as_ref()).is_ok();
 
     Ok(IsWhitelistedResponse { whitelisted })
 }
@@ -94,3 +176,35 @@ pub fn query_unprocessed_reservations(
 
     Ok(UnprocessedReservationsResponse { reservations })
 }
+
+pub fn query_reservation(deps: Deps, token_id: u32) -> Result<ReservationResponse, ContractError> {
+    let user_reservation =
+        reservation::load(deps.storage, token_id).ok_or(ContractError::UnknownReservation {})?;
+
+    Ok(ReservationResponse {
+        token_id: user_reservation.token_id,
+        owner: user_reservation.user_address.to_string(),
+        status: user_reservation.status,
+        paid: user_reservation.paid,
+        reserved_at: user_reservation.reserved_at,
+    })
+}
+
+pub fn query_user_mints(deps: Deps, address: String) -> Result<UserMintsResponse, ContractError> {
+    let user_addr = deps.api.addr_canonicalize(address.as_str())?;
+
+    let stages: StdResult<Vec<StageMints>> = STAGE
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(_, stage)| StageMints {
+                stage_id: stage.id,
+                count: user::load_stage(deps.storage, stage.id, &user_addr),
+            })
+        })
+        .collect();
+
+    Ok(UserMintsResponse {
+        stages: stages?,
+        total: user::load(deps.storage, &user_addr),
+    })
+}
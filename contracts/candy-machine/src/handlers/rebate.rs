@@ -0,0 +1,305 @@
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult,
+    Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::error::ContractError;
+use crate::handlers::mint::public_remaining;
+use crate::msg::{MintStage, ReservationPayment, StageRebate};
+use crate::state::config::{Config, CONFIG};
+use crate::state::rebate::{self, Purchase, Sales};
+use crate::state::reservation;
+use crate::state::stage;
+use crate::state::state::{State, STATE};
+
+fn amount(payment: &ReservationPayment) -> Uint128 {
+    match payment {
+        ReservationPayment::Native(coin) => coin.amount,
+        ReservationPayment::Cw20(coin) => coin.amount,
+    }
+}
+
+fn with_amount(payment: &ReservationPayment, amount: Uint128) -> ReservationPayment {
+    let mut payment = payment.clone();
+    match &mut payment {
+        ReservationPayment::Native(coin) => coin.amount = amount,
+        ReservationPayment::Cw20(coin) => coin.amount = amount,
+    }
+    payment
+}
+
+/// Adds `quantity` tokens bought at `price` to the auction purchases of `owner`.
+/// The part of the price above the floor is held back for rebates.
+pub fn record_purchase(
+    storage: &mut dyn Storage,
+    stage: &MintStage,
+    owner: &Addr,
+    price: &ReservationPayment,
+    quantity: u16,
+) -> Result<(), ContractError> {
+    let stage_id = stage.id;
+    let total = amount(price).checked_mul(Uint128::from(quantity))?;
+
+    let purchase = match rebate::load_purchase(storage, stage_id, owner) {
+        Some(purchase) => Purchase {
+            quantity: purchase.quantity + u32::from(quantity),
+            paid: with_amount(&purchase.paid, amount(&purchase.paid).checked_add(total)?),
+        },
+        None => Purchase {
+            quantity: u32::from(quantity),
+            paid: with_amount(price, total),
+        },
+    };
+
+    rebate::store_purchase(storage, stage_id, owner, &purchase)?;
+    rebate::store_last_price(storage, stage_id, amount(price))?;
+
+    let mut sales = match rebate::load_sales(storage, stage_id) {
+        Some(sales) => Sales {
+            quantity: sales.quantity + u32::from(quantity),
+            paid: with_amount(&sales.paid, amount(&sales.paid).checked_add(total)?),
+            held: sales.held,
+        },
+        None => Sales {
+            quantity: u32::from(quantity),
+            paid: with_amount(price, total),
+            held: Uint128::zero(),
+        },
+    };
+    let floor_total = floor_price(storage, stage).checked_mul(Uint128::from(quantity))?;
+    let held = sales.held + total.saturating_sub(floor_total);
+    hold_sales(storage, stage_id, &mut sales, held)?;
+
+    Ok(())
+}
+
+// the lowest price the remaining auction sales can clear at
+fn floor_price(storage: &dyn Storage, stage: &MintStage) -> Uint128 {
+    let last_price = rebate::load_last_price(storage, stage.id).unwrap_or_default();
+    match &stage.dutch_auction {
+        Some(dutch_auction) => dutch_auction.floor_price.min(last_price),
+        None => last_price,
+    }
+}
+
+// holds or releases the difference to the new held amount of a stage
+fn hold_sales(
+    storage: &mut dyn Storage,
+    stage_id: u8,
+    sales: &mut Sales,
+    held: Uint128,
+) -> StdResult<()> {
+    if held > sales.held {
+        reservation::hold_payment(storage, &with_amount(&sales.paid, held - sales.held))?;
+    } else {
+        reservation::release_payment(storage, &with_amount(&sales.paid, sales.held - held))?;
+    }
+    sales.held = held;
+
+    rebate::store_sales(storage, stage_id, sales)
+}
+
+/// Keeps back what the buyers of every auction stage can still claim: the part
+/// of their payments above the floor price while the auction runs, their
+/// rebates above the clearing price once it is settled.
+pub fn hold_rebates(storage: &mut dyn Storage, now: u64) -> Result<(), ContractError> {
+    let config: Config = CONFIG.load(storage)?;
+    let state: State = STATE.load(storage)?;
+
+    for stage in stage::load_all(storage)? {
+        let mut sales = match rebate::load_sales(storage, stage.id) {
+            Some(sales) => sales,
+            None => continue,
+        };
+
+        let price = match is_settled(storage, &config, &state, &stage, now) {
+            true => rebate::load_last_price(storage, stage.id).unwrap_or_default(),
+            false => floor_price(storage, &stage),
+        };
+        let kept = price.checked_mul(Uint128::from(sales.quantity))?;
+        let held = amount(&sales.paid).saturating_sub(kept);
+        hold_sales(storage, stage.id, &mut sales, held)?;
+    }
+
+    Ok(())
+}
+
+/// Takes `quantity` refunded tokens bought at `price` each out of the auction
+/// purchases of `owner` and returns the amount to refund. Once the rebate was
+/// claimed only the clearing price is still paid for the tokens.
+pub fn refund_purchase(
+    storage: &mut dyn Storage,
+    stage_id: u8,
+    owner: &Addr,
+    price: &ReservationPayment,
+    quantity: u32,
+) -> Result<ReservationPayment, ContractError> {
+    let mut purchase = match rebate::load_purchase(storage, stage_id, owner) {
+        Some(purchase) => purchase,
+        None => {
+            let total = amount(price).checked_mul(Uint128::from(quantity))?;
+            return Ok(with_amount(price, total));
+        }
+    };
+
+    // the average paid never drops below the clearing price, whatever is left
+    // of a higher price goes out with the rebate of the other tokens
+    let mut refund = Uint128::zero();
+    for _ in 0..quantity {
+        let average = amount(&purchase.paid).multiply_ratio(1u128, purchase.quantity);
+        let token_refund = amount(price).min(average);
+        purchase.quantity -= 1;
+        purchase.paid = with_amount(&purchase.paid, amount(&purchase.paid) - token_refund);
+        refund += token_refund;
+    }
+
+    if purchase.quantity == 0 {
+        rebate::remove_purchase(storage, stage_id, owner);
+    } else {
+        rebate::store_purchase(storage, stage_id, owner, &purchase)?;
+    }
+
+    // the refunded tokens no longer need anything held for their rebates
+    if let (Some(mut sales), Some(stage)) = (
+        rebate::load_sales(storage, stage_id),
+        stage::load(storage, stage_id),
+    ) {
+        sales.quantity -= quantity;
+        sales.paid = with_amount(&sales.paid, amount(&sales.paid).saturating_sub(refund));
+        let floor_total =
+            floor_price(storage, &stage).checked_mul(Uint128::from(sales.quantity))?;
+        let held = sales
+            .held
+            .min(amount(&sales.paid).saturating_sub(floor_total));
+        hold_sales(storage, stage_id, &mut sales, held)?;
+    }
+
+    Ok(with_amount(price, refund))
+}
+
+// the clearing price is final once the stage ended or nothing is left to mint in it
+fn is_settled(
+    storage: &dyn Storage,
+    config: &Config,
+    state: &State,
+    stage: &MintStage,
+    now: u64,
+) -> bool {
+    let finished = matches!(stage.finish, Some(finish) if finish < now);
+    let stage_sold_out = matches!(
+        stage.max_supply,
+        Some(max_supply) if stage::load_minted(storage, stage.id) >= max_supply
+    );
+
+    finished || stage_sold_out || public_remaining(config, state) == 0
+}
+
+/// Returns the rebates of `owner` above the latest auction price of every stage.
+pub fn pending_rebates(
+    storage: &dyn Storage,
+    now: u64,
+    owner: &Addr,
+) -> StdResult<Vec<StageRebate>> {
+    let config: Config = CONFIG.load(storage)?;
+    let state: State = STATE.load(storage)?;
+
+    let mut rebates = vec![];
+    for stage in stage::load_all(storage)? {
+        let purchase = match rebate::load_purchase(storage, stage.id, owner) {
+            Some(purchase) => purchase,
+            None => continue,
+        };
+
+        let last_price = rebate::load_last_price(storage, stage.id).unwrap_or_default();
+        let clearing_total = last_price.checked_mul(Uint128::from(purchase.quantity))?;
+        let rebate = amount(&purchase.paid).saturating_sub(clearing_total);
+        if rebate.is_zero() {
+            continue;
+        }
+
+        rebates.push(StageRebate {
+            stage_id: stage.id,
+            rebate: with_amount(&purchase.paid, rebate),
+            claimable: is_settled(storage, &config, &state, &stage, now),
+        });
+    }
+
+    Ok(rebates)
+}
+
+pub fn claim_rebate(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    hold_rebates(deps.storage, env.block.time.seconds())?;
+
+    let rebates: Vec<StageRebate> =
+        pending_rebates(deps.storage, env.block.time.seconds(), &info.sender)?
+            .into_iter()
+            .filter(|rebate| rebate.claimable)
+            .collect();
+    if rebates.is_empty() {
+        return Err(ContractError::NoRebateToClaim {});
+    }
+
+    let mut native: Vec<Coin> = vec![];
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for stage_rebate in rebates.iter() {
+        // only the clearing price stays paid
+        let mut purchase = rebate::load_purchase(deps.storage, stage_rebate.stage_id, &info.sender)
+            .ok_or(ContractError::NoRebateToClaim {})?;
+        purchase.paid = with_amount(
+            &purchase.paid,
+            amount(&purchase.paid) - amount(&stage_rebate.rebate),
+        );
+        rebate::store_purchase(deps.storage, stage_rebate.stage_id, &info.sender, &purchase)?;
+
+        if let Some(mut sales) = rebate::load_sales(deps.storage, stage_rebate.stage_id) {
+            let claimed = amount(&stage_rebate.rebate);
+            sales.paid = with_amount(&sales.paid, amount(&sales.paid).saturating_sub(claimed));
+            let held = sales.held.saturating_sub(claimed);
+            hold_sales(deps.storage, stage_rebate.stage_id, &mut sales, held)?;
+        }
+
+        match &stage_rebate.rebate {
+            ReservationPayment::Native(coin) => {
+                match native.iter_mut().find(|native| native.denom == coin.denom) {
+                    Some(native) => native.amount += coin.amount,
+                    None => native.push(coin.clone()),
+                }
+            }
+            ReservationPayment::Cw20(coin) => messages.push(
+                WasmMsg::Execute {
+                    contract_addr: coin.address.clone(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: info.sender.to_string(),
+                        amount: coin.amount,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            ),
+        }
+    }
+
+    if !native.is_empty() {
+        messages.insert(
+            0,
+            BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: native,
+            }
+            .into(),
+        );
+    }
+
+    let stage_ids = rebates
+        .iter()
+        .map(|rebate| rebate.stage_id.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_rebate")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("stage_ids", stage_ids)
+        .add_messages(messages))
+}
@@ -0,0 +1,200 @@
+use crate::error::ContractError;
+use crate::handlers::mint::is_hidden;
+use crate::handlers::ownership::assert_owner;
+use crate::handlers::rebate::refund_purchase;
+use crate::msg::{ReservationPayment, ReservationStatus};
+use crate::state::config::{Config, CONFIG};
+use crate::state::queue;
+use crate::state::reservation;
+use crate::state::stage;
+use crate::state::state::{State, STATE};
+use cosmwasm_std::{attr, to_binary, Addr, BankMsg, DepsMut, Env, MessageInfo, Response, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+pub fn claim_refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: u32,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let reservation_deadline = config
+        .reservation_deadline
+        .ok_or(ContractError::RefundsDisabled {})?;
+
+    let mut user_reservation =
+        reservation::load(deps.storage, token_id).ok_or(ContractError::UnknownReservation {})?;
+
+    if user_reservation.user_address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if user_reservation.status != ReservationStatus::Pending {
+        return Err(ContractError::ReservationAlreadyProcessed { token_id });
+    }
+
+    let deadline = user_reservation
+        .reserved_at
+        .saturating_add(reservation_deadline);
+    if env.block.time.seconds() <= deadline {
+        return Err(ContractError::ReservationDeadlineNotReached { deadline });
+    }
+
+    // cancel the reservation and release its token id
+    user_reservation.status = ReservationStatus::Cancelled;
+    reservation::store(deps.storage, token_id, &user_reservation)?;
+    reservation::remove_unprocessed(deps.storage, token_id);
+    reservation::store_released(deps.storage, token_id)?;
+    if let Some(paid) = &user_reservation.paid {
+        reservation::release_payment(deps.storage, paid)?;
+    }
+    if let Some(stage_id) = user_reservation.stage_id {
+        let stage_minted = stage::load_minted(deps.storage, stage_id);
+        stage::store_minted(deps.storage, stage_id, stage_minted.saturating_sub(1))?;
+    }
+
+    // auction tokens are refunded net of the rebates already claimed
+    let refund = match (user_reservation.stage_id, &user_reservation.paid) {
+        (Some(stage_id), Some(paid)) => Some(refund_purchase(
+            deps.storage,
+            stage_id,
+            &info.sender,
+            paid,
+            1,
+        )?),
+        _ => user_reservation.paid.clone(),
+    };
+
+    let mut state: State = STATE.load(deps.storage)?;
+    state.token_count -= 1;
+    // the token goes back to the team reserve it was airdropped from
+    if user_reservation.airdropped {
+        state.airdropped -= 1;
+    }
+    STATE.save(deps.storage, &state)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "claim_refund")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("token_id", token_id.to_string());
+
+    add_refund(resp, &info.sender, refund)
+}
+
+/// Cancels a queued mint of a drop the owner did not reveal within the
+/// reservation deadline and refunds its payment.
+pub fn cancel_queued_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    queue_id: u32,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let reservation_deadline = config
+        .reservation_deadline
+        .ok_or(ContractError::RefundsDisabled {})?;
+
+    let mint = queue::load(deps.storage, queue_id).ok_or(ContractError::UnknownQueuedMint {})?;
+    if mint.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // once revealed the outcome is known, the mint can only be settled
+    if !is_hidden(deps.storage)? {
+        return Err(ContractError::DropAlreadyRevealed {});
+    }
+
+    let deadline = mint.queued_at.saturating_add(reservation_deadline);
+    if env.block.time.seconds() <= deadline {
+        return Err(ContractError::ReservationDeadlineNotReached { deadline });
+    }
+
+    queue::remove(deps.storage, queue_id, &mint)?;
+    if let Some(stage_id) = mint.stage_id {
+        let stage_minted = stage::load_minted(deps.storage, stage_id);
+        stage::store_minted(
+            deps.storage,
+            stage_id,
+            stage_minted.saturating_sub(mint.quantity),
+        )?;
+    }
+
+    let refund = match (mint.stage_id, &mint.paid) {
+        (Some(stage_id), Some(paid)) => Some(refund_purchase(
+            deps.storage,
+            stage_id,
+            &info.sender,
+            paid,
+            mint.quantity,
+        )?),
+        _ => None,
+    };
+
+    let mut state: State = STATE.load(deps.storage)?;
+    state.token_count -= mint.quantity;
+    state.queued -= mint.quantity;
+    // only airdrops are queued outside of the mint stages
+    if mint.stage_id.is_none() {
+        state.airdropped -= mint.quantity;
+    }
+    STATE.save(deps.storage, &state)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "cancel_queued_mint")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("queue_id", queue_id.to_string());
+
+    add_refund(resp, &info.sender, refund)
+}
+
+fn add_refund(
+    resp: Response,
+    recipient: &Addr,
+    refund: Option<ReservationPayment>,
+) -> Result<Response, ContractError> {
+    let resp = match refund {
+        Some(ReservationPayment::Native(coin)) => resp
+            .add_attribute("amount", coin.to_string())
+            .add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![coin],
+            }),
+        Some(ReservationPayment::Cw20(coin)) => resp
+            .add_attribute("cw20_amount", format!("{}{}", coin.amount, coin.address))
+            .add_message(WasmMsg::Execute {
+                contract_addr: coin.address,
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: coin.amount,
+                })?,
+                funds: vec![],
+            }),
+        None => resp,
+    };
+
+    Ok(resp)
+}
+
+/// Sets the seconds after which pending reservations and queued mints can be
+/// refunded, refunds are disabled without a deadline.
+pub fn set_reservation_deadline(
+    deps: DepsMut,
+    info: MessageInfo,
+    reservation_deadline: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = assert_owner(deps.storage, &info.sender)?;
+
+    config.reservation_deadline = reservation_deadline;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_reservation_deadline"),
+        attr("sender", info.sender.to_string()),
+        attr(
+            "reservation_deadline",
+            reservation_deadline
+                .map(|reservation_deadline| reservation_deadline.to_string())
+                .unwrap_or_default(),
+        ),
+    ]))
+}
@@ -0,0 +1,74 @@
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::handlers::ownership::assert_owner;
+use crate::state::collection_kind::CollectionKind;
+use crate::state::config::Config;
+use crate::state::reveal::{Reveal, REVEAL};
+use crate::state::state::STATE;
+
+fn assert_single(config: &Config) -> Result<(), ContractError> {
+    match config.collection_kind {
+        CollectionKind::Single { .. } => Ok(()),
+        CollectionKind::Collectible { .. } => Err(ContractError::InvalidCollectionKind {}),
+    }
+}
+
+pub fn commit_reveal(
+    deps: DepsMut,
+    info: MessageInfo,
+    base_uri_hash: String,
+) -> Result<Response, ContractError> {
+    let config: Config = assert_owner(deps.storage, &info.sender)?;
+    assert_single(&config)?;
+
+    // the commitment has to be made before the first token is minted or queued
+    if STATE.load(deps.storage)?.token_count > 0 {
+        return Err(ContractError::RevealAlreadyStarted {});
+    }
+    if base64::decode(&base_uri_hash)?.len() != 32 {
+        return Err(ContractError::InvalidBaseUriHash {});
+    }
+
+    REVEAL.save(
+        deps.storage,
+        &Reveal {
+            base_uri_hash,
+            base_uri: None,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "commit_reveal"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
+
+pub fn reveal(
+    deps: DepsMut,
+    info: MessageInfo,
+    base_uri: String,
+) -> Result<Response, ContractError> {
+    let config: Config = assert_owner(deps.storage, &info.sender)?;
+    assert_single(&config)?;
+
+    let mut reveal = REVEAL
+        .may_load(deps.storage)?
+        .ok_or(ContractError::RevealNotCommitted {})?;
+    if reveal.base_uri.is_some() {
+        return Err(ContractError::AlreadyRevealed {});
+    }
+    if base64::encode(Sha256::digest(base_uri.as_bytes())) != reveal.base_uri_hash {
+        return Err(ContractError::InvalidBaseUri {});
+    }
+
+    reveal.base_uri = Some(base_uri.clone());
+    REVEAL.save(deps.storage, &reveal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "reveal"),
+        attr("sender", info.sender.to_string()),
+        attr("base_uri", base_uri),
+    ]))
+}
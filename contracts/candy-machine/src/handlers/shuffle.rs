@@ -0,0 +1,114 @@
+use cosmwasm_std::{attr, Addr, Binary, DepsMut, Env, MessageInfo, Response, Storage};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+
+use crate::error::ContractError;
+use crate::handlers::ownership::assert_owner;
+use crate::msg::ConfigureShuffleMsg;
+use crate::state::reservation;
+use crate::state::shuffle::{self, Shuffle, SHUFFLE};
+use crate::state::state::STATE;
+
+pub fn configure_shuffle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ConfigureShuffleMsg,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let shuffle = SHUFFLE.may_load(deps.storage)?;
+
+    match msg {
+        ConfigureShuffleMsg::Commit { seed_hash } => {
+            // the pool can only be shuffled before any token id was handed out
+            let state = STATE.load(deps.storage)?;
+            if state.token_count + reservation::released_count(deps.storage) > 0
+                || matches!(
+                    shuffle,
+                    Some(Shuffle {
+                        entropy: Some(_),
+                        ..
+                    })
+                )
+            {
+                return Err(ContractError::ShuffleAlreadyStarted {});
+            }
+            if base64::decode(&seed_hash)?.len() != 32 {
+                return Err(ContractError::InvalidShuffleSeedHash {});
+            }
+
+            SHUFFLE.save(
+                deps.storage,
+                &Shuffle {
+                    seed_hash,
+                    entropy: None,
+                },
+            )?;
+        }
+        ConfigureShuffleMsg::Reveal { seed } => {
+            let mut shuffle = shuffle.ok_or(ContractError::ShuffleSeedNotCommitted {})?;
+            if shuffle.entropy.is_some() {
+                return Err(ContractError::ShuffleSeedAlreadyRevealed {});
+            }
+            if base64::encode(Sha256::digest(seed.as_bytes())) != shuffle.seed_hash {
+                return Err(ContractError::InvalidShuffleSeed {});
+            }
+
+            let mut hasher = Sha256::new();
+            hasher.input(seed.as_bytes());
+            hasher.input(env.block.height.to_be_bytes());
+            hasher.input(env.block.time.nanos().to_be_bytes());
+            shuffle.entropy = Some(Binary::from(hasher.result().to_vec()));
+
+            SHUFFLE.save(deps.storage, &shuffle)?;
+        }
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "configure_shuffle"),
+        attr("sender", info.sender.to_string()),
+    ]))
+}
+
+/// Draws `count` new token ids, either sequentially or from the shuffled pool.
+/// `assigned` is the number of token ids handed out so far.
+///
+/// Ids are only drawn once the seed is revealed and public minting is closed.
+/// Every draw mixes in the recipient, which was fixed when the mint was queued
+/// before anyone could know the seed. Airdrops after the reveal only draw once
+/// every queued mint is settled.
+pub fn draw_token_ids(
+    storage: &mut dyn Storage,
+    recipient: &Addr,
+    max_token_count: u32,
+    assigned: u32,
+    count: u32,
+) -> Result<Vec<u32>, ContractError> {
+    let mut shuffle = match SHUFFLE.may_load(storage)? {
+        Some(shuffle) => shuffle,
+        None => return Ok((assigned + 1..assigned + 1 + count).collect()),
+    };
+    let mut entropy = shuffle
+        .entropy
+        .ok_or(ContractError::ShuffleSeedNotRevealed {})?
+        .to_vec();
+
+    let mut token_ids = vec![];
+    for drawn in 0..count {
+        let mut hasher = Sha256::new();
+        hasher.input(&entropy);
+        hasher.input(recipient.as_bytes());
+        entropy = hasher.result().to_vec();
+
+        let remaining = max_token_count - assigned - drawn;
+        let random = u64::from_be_bytes(entropy[0..8].try_into().unwrap());
+        let position = (random % u64::from(remaining)) as u32;
+        token_ids.push(shuffle::draw(storage, position, remaining)?);
+    }
+
+    shuffle.entropy = Some(Binary::from(entropy));
+    SHUFFLE.save(storage, &shuffle)?;
+
+    Ok(token_ids)
+}
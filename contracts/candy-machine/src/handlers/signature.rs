@@ -0,0 +1,97 @@
+use cosmwasm_std::{Addr, Api, DepsMut, Env};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{ActivePublicKey, KeyType, ReservationSignature};
+use crate::state::reservation;
+
+/// Verifies one bot protection signature per reserved token and marks their
+/// nonces as used. All signatures of a batch must be issued by the same key,
+/// any of the active keys is accepted.
+pub fn verify_reservation_signatures(
+    deps: DepsMut,
+    env: &Env,
+    owner: &Addr,
+    stage_id: u8,
+    public_keys: &[ActivePublicKey],
+    signatures: &[ReservationSignature],
+) -> Result<(), ContractError> {
+    let mut hashes: Vec<Vec<u8>> = Vec::with_capacity(signatures.len());
+    let mut decoded_signatures: Vec<Vec<u8>> = Vec::with_capacity(signatures.len());
+    let mut nonces: Vec<u64> = Vec::with_capacity(signatures.len());
+
+    for signature in signatures {
+        if env.block.time.seconds() > signature.expires_at {
+            return Err(ContractError::SignatureExpired {
+                expires_at: signature.expires_at,
+            });
+        }
+
+        if reservation::is_nonce_used(deps.storage, signature.nonce)
+            || nonces.contains(&signature.nonce)
+        {
+            return Err(ContractError::NonceAlreadyUsed {
+                nonce: signature.nonce,
+            });
+        }
+        nonces.push(signature.nonce);
+
+        // bind the signature to this contract, stage and chain
+        let payload = format!(
+            "{}/{}/{}/{}/{}/{}",
+            env.block.chain_id,
+            env.contract.address,
+            stage_id,
+            owner,
+            signature.expires_at,
+            signature.nonce
+        );
+        hashes.push(Sha256::digest(payload.as_bytes()).to_vec());
+        decoded_signatures.push(base64::decode(&signature.signature)?);
+    }
+
+    // fall back to the next key, reporting the last error if none matches
+    let mut error = ContractError::InvalidSignature {};
+    for public_key in public_keys {
+        match verify_with_key(deps.api, public_key, &hashes, &decoded_signatures) {
+            Ok(true) => {
+                for nonce in nonces {
+                    reservation::store_used_nonce(deps.storage, nonce)?;
+                }
+                return Ok(());
+            }
+            Ok(false) => (),
+            Err(e) => error = e,
+        }
+    }
+
+    Err(error)
+}
+
+fn verify_with_key(
+    api: &dyn Api,
+    public_key: &ActivePublicKey,
+    hashes: &[Vec<u8>],
+    signatures: &[Vec<u8>],
+) -> Result<bool, ContractError> {
+    let decoded_public_key = base64::decode(&public_key.public_key)?;
+
+    match public_key.key_type {
+        KeyType::Secp256k1 => {
+            for (hash, signature) in hashes.iter().zip(signatures.iter()) {
+                if !api.secp256k1_verify(hash, signature, &decoded_public_key)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        KeyType::Ed25519 => {
+            let messages: Vec<&[u8]> = hashes.iter().map(|hash| hash.as_slice()).collect();
+            let signatures: Vec<&[u8]> = signatures
+                .iter()
+                .map(|signature| signature.as_slice())
+                .collect();
+            Ok(api.ed25519_batch_verify(&messages, &signatures, &[&decoded_public_key])?)
+        }
+    }
+}
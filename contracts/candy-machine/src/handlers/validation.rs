@@ -0,0 +1,125 @@
+use cosmwasm_std::Api;
+
+use crate::error::ContractError;
+use crate::handlers::whitelist::decode_merkle_hash;
+use crate::msg::{DutchAuction, MintStage};
+
+/// Validates the complete set of mint stages of a new contract.
+pub fn validate_mint_stages(api: &dyn Api, stages: &[MintStage]) -> Result<(), ContractError> {
+    for (i, stage) in stages.iter().enumerate() {
+        validate_mint_stage(api, stage)?;
+
+        for other in stages.iter().skip(i + 1) {
+            if stage.id == other.id {
+                return Err(ContractError::MintStageAlreadyExists { id: stage.id });
+            }
+            validate_mint_stage_overlap(stage, other)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates a mint stage written next to the stored ones. Only the written
+/// stage is checked for overlaps, stages migrated with overlapping time frames
+/// stay editable.
+pub fn validate_mint_stage_against(
+    api: &dyn Api,
+    stage: &MintStage,
+    others: &[MintStage],
+) -> Result<(), ContractError> {
+    validate_mint_stage(api, stage)?;
+
+    others
+        .iter()
+        .filter(|other| other.id != stage.id)
+        .try_for_each(|other| validate_mint_stage_overlap(stage, other))
+}
+
+pub fn validate_mint_stage(api: &dyn Api, stage: &MintStage) -> Result<(), ContractError> {
+    if let (Some(start), Some(finish)) = (stage.start, stage.finish) {
+        if start >= finish {
+            return Err(ContractError::InvalidMintStageTime { start, finish });
+        }
+    }
+
+    if stage.max_per_user == Some(0) {
+        return Err(ContractError::InvalidMintStageMaxPerUser { id: stage.id });
+    }
+
+    if stage.max_supply == Some(0) {
+        return Err(ContractError::InvalidMintStageMaxSupply { id: stage.id });
+    }
+
+    if let Some(price) = &stage.price {
+        if price.amount.is_zero() {
+            return Err(ContractError::InvalidMintStagePrice { id: stage.id });
+        }
+    }
+
+    if let Some(cw20_price) = &stage.cw20_price {
+        if cw20_price.amount.is_zero() {
+            return Err(ContractError::InvalidMintStagePrice { id: stage.id });
+        }
+        api.addr_validate(&cw20_price.address)?;
+    }
+
+    if let Some(dutch_auction) = &stage.dutch_auction {
+        validate_dutch_auction(stage, dutch_auction)?;
+    }
+
+    if let Some(merkle_root) = &stage.merkle_root {
+        decode_merkle_hash(merkle_root).ok_or(ContractError::InvalidMerkleRoot { id: stage.id })?;
+        // proofs are only checked for whitelisted stages
+        if !stage.whitelist_enabled {
+            return Err(ContractError::MerkleRootWhitelistDisabled { id: stage.id });
+        }
+    }
+
+    Ok(())
+}
+
+// auctions need a time frame to decay in and a single price above the floor
+fn validate_dutch_auction(
+    stage: &MintStage,
+    dutch_auction: &DutchAuction,
+) -> Result<(), ContractError> {
+    let invalid = ContractError::InvalidDutchAuction { id: stage.id };
+
+    if stage.start.is_none() || stage.finish.is_none() || dutch_auction.steps == 0 {
+        return Err(invalid);
+    }
+    // rebates are paid back in the single currency of the auction
+    if dutch_auction.floor_price.is_zero() || stage.price.is_some() == stage.cw20_price.is_some() {
+        return Err(invalid);
+    }
+
+    let prices = stage
+        .price
+        .iter()
+        .map(|price| price.amount)
+        .chain(stage.cw20_price.iter().map(|price| price.amount));
+    for amount in prices {
+        if dutch_auction.floor_price >= amount {
+            return Err(invalid);
+        }
+    }
+
+    Ok(())
+}
+
+// stages without start or finish are open ended
+fn validate_mint_stage_overlap(stage: &MintStage, other: &MintStage) -> Result<(), ContractError> {
+    let start = stage.start.unwrap_or(u64::MIN);
+    let finish = stage.finish.unwrap_or(u64::MAX);
+    let other_start = other.start.unwrap_or(u64::MIN);
+    let other_finish = other.finish.unwrap_or(u64::MAX);
+
+    if start <= other_finish && other_start <= finish {
+        return Err(ContractError::MintStageOverlap {
+            id: stage.id.min(other.id),
+        });
+    }
+
+    Ok(())
+}
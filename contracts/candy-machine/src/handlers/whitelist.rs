@@ -0,0 +1,75 @@
+use cosmwasm_std::{Addr, Deps};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{MerkleProof, MintStage};
+use crate::state::user;
+
+/// Checks whether an address may mint in a whitelisted stage, returning the
+/// per-address allowance carried by a valid merkle proof.
+pub fn check_whitelisted(
+    deps: Deps,
+    stage: &MintStage,
+    address: &Addr,
+    merkle_proof: Option<&MerkleProof>,
+) -> Result<Option<u16>, ContractError> {
+    if let (Some(merkle_root), Some(merkle_proof)) = (&stage.merkle_root, merkle_proof) {
+        if verify_merkle_proof(merkle_root, address, merkle_proof)? {
+            return Ok(merkle_proof.allowance);
+        }
+    }
+
+    let canonical_addr = deps.api.addr_canonicalize(address.as_str())?;
+    if user::is_whitelisted(deps.storage, stage.id, &canonical_addr) {
+        return Ok(None);
+    }
+
+    Err(ContractError::NotAllowNonWhitelisted {
+        address: address.to_string(),
+    })
+}
+
+pub fn decode_merkle_hash(hash: &str) -> Option<[u8; 32]> {
+    let bytes = base64::decode(hash).ok()?;
+    if bytes.len() != 32 {
+        return None;
+    }
+
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&bytes);
+    Some(result)
+}
+
+fn verify_merkle_proof(
+    merkle_root: &str,
+    address: &Addr,
+    merkle_proof: &MerkleProof,
+) -> Result<bool, ContractError> {
+    let merkle_root =
+        decode_merkle_hash(merkle_root).ok_or(ContractError::InvalidMerkleProof {})?;
+
+    let leaf = match merkle_proof.allowance {
+        Some(allowance) => Sha256::digest(format!("{}:{}", address, allowance).as_bytes()),
+        None => Sha256::digest(address.as_bytes()),
+    };
+
+    // siblings are hashed in sorted order so proofs don't need to carry positions
+    let hash = merkle_proof
+        .proof
+        .iter()
+        .try_fold(leaf.into(), |hash: [u8; 32], sibling| {
+            let sibling =
+                decode_merkle_hash(sibling).ok_or(ContractError::InvalidMerkleProof {})?;
+            let (first, second) = match hash <= sibling {
+                true => (hash, sibling),
+                false => (sibling, hash),
+            };
+
+            let mut hasher = Sha256::new();
+            hasher.input(first);
+            hasher.input(second);
+            Ok::<[u8; 32], ContractError>(hasher.result().into())
+        })?;
+
+    Ok(hash == merkle_root)
+}
@@ -1,21 +1,137 @@
 use crate::error::ContractError;
-use crate::state::config::{Config, CONFIG};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use crate::handlers::ownership::assert_owner;
+use crate::handlers::rebate::hold_rebates;
+use crate::state::reservation;
+use cosmwasm_std::{
+    to_binary, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20CoinVerified, Cw20ExecuteMsg, Cw20QueryMsg};
 
 pub fn withdraw_funds(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     recipient: String,
+    amount: Option<Vec<Coin>>,
+    cw20_tokens: Option<Vec<String>>,
 ) -> Result<Response, ContractError> {
     deps.api.addr_validate(&recipient)?;
 
-    let config: Config = CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
-        return Err(ContractError::Unauthorized {});
+    assert_owner(deps.storage, &info.sender)?;
+
+    // payments of pending reservations stay in the contract for refunds, the
+    // auction payments above the clearing price for rebates
+    hold_rebates(deps.storage, env.block.time.seconds())?;
+    let balances: Vec<Coin> = deps
+        .querier
+        .query_all_balances(env.contract.address.clone())?
+        .into_iter()
+        .map(|coin| Coin {
+            amount: coin
+                .amount
+                .saturating_sub(reservation::held_native(deps.storage, &coin.denom)),
+            denom: coin.denom,
+        })
+        .collect();
+
+    // withdraw everything unless a subset of coins is requested
+    let withdraw_amount: Vec<Coin> = match amount {
+        Some(requested) => requested
+            .into_iter()
+            .filter(|coin| !coin.amount.is_zero())
+            .map(|coin| {
+                let available = balances
+                    .iter()
+                    .find(|c| c.denom == coin.denom)
+                    .map(|c| c.amount)
+                    .unwrap_or_else(Uint128::zero);
+                if available < coin.amount {
+                    return Err(ContractError::InsufficientFunds {
+                        denom: coin.denom,
+                        available,
+                    });
+                }
+                Ok(coin)
+            })
+            .collect::<Result<Vec<Coin>, ContractError>>()?,
+        None => balances
+            .into_iter()
+            .filter(|coin| !coin.amount.is_zero())
+            .collect(),
+    };
+
+    // sweep the whole balance of every requested cw20 token
+    let cw20_withdraw_amount: Vec<Cw20CoinVerified> = cw20_tokens
+        .unwrap_or_default()
+        .iter()
+        .map(|token| {
+            let address = deps.api.addr_validate(token)?;
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                address.clone(),
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            let held = reservation::held_cw20(deps.storage, address.as_str());
+            Ok(Cw20CoinVerified {
+                address,
+                amount: res.balance.saturating_sub(held),
+            })
+        })
+        .collect::<StdResult<Vec<Cw20CoinVerified>>>()?
+        .into_iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+
+    if withdraw_amount.is_empty() && cw20_withdraw_amount.is_empty() {
+        return Err(ContractError::NoFundsToWithdraw {});
     }
 
-    Ok(Response::new()
+    let mut resp = Response::new()
         .add_attribute("action", "withdraw_funds")
-        .add_attribute("recipient", &recipient))
+        .add_attribute("recipient", &recipient);
+
+    if !withdraw_amount.is_empty() {
+        let withdrawn = withdraw_amount
+            .iter()
+            .map(|coin| coin.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+
+        resp = resp
+            .add_message(BankMsg::Send {
+                to_address: recipient.clone(),
+                amount: withdraw_amount,
+            })
+            .add_attribute("amount", withdrawn);
+    }
+
+    if !cw20_withdraw_amount.is_empty() {
+        let withdrawn = cw20_withdraw_amount
+            .iter()
+            .map(|coin| format!("{}{}", coin.amount, coin.address))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let transfer_msgs = cw20_withdraw_amount
+            .into_iter()
+            .map(|coin| {
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: coin.address.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.clone(),
+                        amount: coin.amount,
+                    })?,
+                    funds: vec![],
+                }))
+            })
+            .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+        resp = resp
+            .add_messages(transfer_msgs)
+            .add_attribute("cw20_amount", withdrawn);
+    }
+
+    Ok(resp)
 }
@@ -1,4 +1,5 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Coin, Uint128};
+use cw20::{Cw20Coin, Cw20ReceiveMsg};
 use cw721_metadata_onchain::Metadata as Cw721Metadata;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -8,14 +9,27 @@ use serde::{Deserialize, Serialize};
 pub enum CollectionKind {
     Single {
         image: String,
+        base_uri: Option<String>,
+        token_uri_suffix: Option<String>,
+        onchain_metadata: Option<bool>,
+        base_uri_frozen: Option<bool>,
     },
     Collectible {
         minter: String,
         cover: String,
         public_key: Option<String>,
+        key_type: Option<KeyType>,
     },
 }
 
+/// Signature scheme of the collectible bot protection key, secp256k1 when not set.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyType {
+    Secp256k1,
+    Ed25519,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub nft_code_id: u64,
@@ -23,10 +37,14 @@ pub struct InstantiateMsg {
     pub symbol: String,
     pub description: String,
     pub max_token_count: u32,
+    pub max_per_user: Option<u16>,
     pub mint_stages: Vec<MintStage>,
     pub collection_kind: CollectionKind,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum ConfigureMintStageMsg {
     Config {
@@ -34,13 +52,68 @@ pub enum ConfigureMintStageMsg {
         start: Option<u64>,
         finish: Option<u64>,
         max_per_user: Option<u16>,
-        price: Option<Uint128>,
+        price: Option<Coin>,
+        cw20_price: Option<Cw20Coin>,
         whitelist_enabled: Option<bool>,
+        max_supply: Option<u32>,
     },
     Whitelist {
         whitelist: bool,
         candidates: Vec<String>,
     },
+    MerkleRoot {
+        merkle_root: Option<String>,
+    },
+    DutchAuction {
+        dutch_auction: Option<DutchAuction>,
+    },
+}
+
+/// Manages the collectible bot protection keys. A secondary key is accepted
+/// next to the primary one until it expires, e.g. while rotating keys.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ConfigurePublicKeyMsg {
+    Rotate {
+        public_key: String,
+        key_type: Option<KeyType>,
+    },
+    AddSecondary {
+        public_key: String,
+        key_type: Option<KeyType>,
+        expires_at: u64,
+    },
+    Remove {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigureShuffleMsg {
+    Commit {
+        seed_hash: String,
+    },
+    /// Reveals the committed seed so queued mints can be settled. Stage mints
+    /// close with the reveal, since later draws could be computed in advance.
+    Reveal {
+        seed: String,
+    },
+}
+
+/// Signature issued by the bot protection backend for a single collectible
+/// reservation, one is required per reserved token. The signed message is the
+/// sha256 of `{chain_id}/{contract_address}/{stage_id}/{owner}/{expires_at}/{nonce}`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReservationSignature {
+    pub signature: String,
+    pub expires_at: u64,
+    pub nonce: u64,
+}
+
+/// Proof of inclusion in a mint stage merkle root. Leaves are the sha256 of
+/// the address, or of `{address}:{allowance}` when the allowance is set.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleProof {
+    pub proof: Vec<String>,
+    pub allowance: Option<u16>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -48,27 +121,107 @@ pub enum ConfigureMintStageMsg {
 pub enum ExecuteMsg {
     Mint {
         stage_id: u8,
-        signature: Option<String>,
+        quantity: Option<u16>,
+        signatures: Option<Vec<ReservationSignature>>,
+        merkle_proof: Option<MerkleProof>,
     },
     Reserve {
         stage_id: u8,
-        signature: Option<String>,
+        quantity: Option<u16>,
+        signatures: Option<Vec<ReservationSignature>>,
+        merkle_proof: Option<MerkleProof>,
     },
     MinterMint {
         token_id: u32,
         metadata: Cw721Metadata,
     },
+    MinterMintBatch {
+        items: Vec<(u32, Cw721Metadata)>,
+    },
     Configure {
         name: Option<String>,
         description: Option<String>,
         nft_address: Option<String>,
+        max_per_user: Option<u16>,
+    },
+    SetReservationDeadline {
+        reservation_deadline: Option<u64>,
+    },
+    SetTeamReserve {
+        team_reserve: Option<u32>,
+    },
+    Airdrop {
+        recipients: Vec<(String, u32)>,
+    },
+    SettleMints {
+        limit: Option<u32>,
+    },
+    CancelQueuedMint {
+        queue_id: u32,
     },
     ConfigureMintStage {
         id: u8,
         config: ConfigureMintStageMsg,
     },
+    ConfigurePublicKey {
+        config: ConfigurePublicKeyMsg,
+    },
+    ConfigureShuffle {
+        config: ConfigureShuffleMsg,
+    },
+    AddMintStage {
+        stage: MintStage,
+    },
+    RemoveMintStage {
+        id: u8,
+    },
     WithdrawFunds {
         recipient: String,
+        amount: Option<Vec<Coin>>,
+        cw20_tokens: Option<Vec<String>>,
+    },
+    Receive(Cw20ReceiveMsg),
+    ProposeNewOwner {
+        owner: String,
+    },
+    AcceptOwnership {},
+    CancelOwnershipProposal {},
+    Pause {},
+    Unpause {},
+    SetPauser {
+        pauser: Option<String>,
+    },
+    ClaimRefund {
+        token_id: u32,
+    },
+    ClaimRebate {},
+    ConfigureBaseUri {
+        base_uri: Option<String>,
+        token_uri_suffix: Option<String>,
+        freeze: Option<bool>,
+    },
+    CommitReveal {
+        base_uri_hash: String,
+    },
+    Reveal {
+        base_uri: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    Mint {
+        stage_id: u8,
+        quantity: Option<u16>,
+        signatures: Option<Vec<ReservationSignature>>,
+        merkle_proof: Option<MerkleProof>,
+    },
+    Reserve {
+        stage_id: u8,
+        quantity: Option<u16>,
+        signatures: Option<Vec<ReservationSignature>>,
+        merkle_proof: Option<MerkleProof>,
     },
 }
 
@@ -83,28 +236,73 @@ pub enum QueryMsg {
     MintStage {
         stage_id: u8,
     },
+    CurrentPrice {
+        stage_id: u8,
+    },
     IsWhitelisted {
         stage_id: u8,
         address: String,
+        merkle_proof: Option<MerkleProof>,
     },
     UnprocessedReservations {
         start_after: Option<u32>,
         limit: Option<u32>,
     },
+    UserMints {
+        address: String,
+    },
+    PendingRebate {
+        address: String,
+    },
+    Reservation {
+        token_id: u32,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
+    pub owner: String,
+    pub pending_owner: Option<String>,
+    pub pauser: Option<String>,
     pub nft_address: Option<String>,
     pub name: String,
     pub description: String,
     pub collection_kind: CollectionKind,
     pub max_token_count: u32,
+    pub max_per_user: Option<u16>,
+    pub reservation_deadline: Option<u64>,
+    pub team_reserve: Option<u32>,
+    pub active_public_keys: Vec<ActivePublicKey>,
+    pub shuffle: Option<ShuffleResponse>,
+    pub reveal: Option<RevealResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ShuffleResponse {
+    pub seed_hash: String,
+    // stage mints are closed once the seed is revealed
+    pub revealed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RevealResponse {
+    pub base_uri_hash: String,
+    pub base_uri: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActivePublicKey {
+    pub public_key: String,
+    pub key_type: KeyType,
+    pub expires_at: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StateResponse {
     pub token_count: u32,
+    pub paused: bool,
+    pub airdropped: u32,
+    pub queued: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -114,7 +312,7 @@ pub struct IsWhitelistedResponse {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MintStagesResponse {
-    pub mint_stages: Vec<MintStage>,
+    pub mint_stages: Vec<MintStageResponse>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -124,11 +322,87 @@ pub struct MintStage {
     pub start: Option<u64>,
     pub finish: Option<u64>,
     pub max_per_user: Option<u16>,
-    pub price: Option<Uint128>,
+    pub price: Option<Coin>,
+    pub cw20_price: Option<Cw20Coin>,
     pub whitelist_enabled: bool,
+    pub merkle_root: Option<String>,
+    pub max_supply: Option<u32>,
+    pub dutch_auction: Option<DutchAuction>,
+}
+
+/// Lowers the stage price down to `floor_price` in `steps` equal drops between
+/// the stage start and finish.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DutchAuction {
+    pub floor_price: Uint128,
+    pub steps: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CurrentPriceResponse {
+    pub price: Option<Coin>,
+    pub cw20_price: Option<Cw20Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintStageResponse {
+    pub stage: MintStage,
+    pub minted: u32,
+    // tokens left in the stage, limited by the collection supply
+    pub remaining: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UnprocessedReservationsResponse {
     pub reservations: Vec<u32>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationStatus {
+    Pending,
+    Minted,
+    Cancelled,
+}
+
+/// Amount paid in native or cw20 tokens.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationPayment {
+    Native(Coin),
+    Cw20(Cw20Coin),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StageRebate {
+    pub stage_id: u8,
+    pub rebate: ReservationPayment,
+    // false until the stage ended or sold out
+    pub claimable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRebateResponse {
+    pub rebates: Vec<StageRebate>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReservationResponse {
+    pub token_id: u32,
+    pub owner: String,
+    pub status: ReservationStatus,
+    pub paid: Option<ReservationPayment>,
+    pub reserved_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StageMints {
+    pub stage_id: u8,
+    pub count: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserMintsResponse {
+    pub stages: Vec<StageMints>,
+    pub total: u16,
+}
@@ -1,4 +1,4 @@
-use crate::msg::CollectionKind as CollectionKindMsg;
+use crate::msg::{ActivePublicKey, CollectionKind as CollectionKindMsg, KeyType};
 use cosmwasm_std::{Addr, Api, StdResult};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -7,25 +7,54 @@ use serde::{Deserialize, Serialize};
 pub enum CollectionKind {
     Single {
         image: String,
+        base_uri: Option<String>,
+        token_uri_suffix: Option<String>,
+        onchain_metadata: Option<bool>,
+        #[serde(default)]
+        base_uri_frozen: bool,
     },
     Collectible {
         minter: Addr,
         cover: String,
         public_key: Option<String>,
+        key_type: Option<KeyType>,
+        secondary_key: Option<SecondaryPublicKey>,
     },
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SecondaryPublicKey {
+    pub public_key: String,
+    pub key_type: Option<KeyType>,
+    pub expires_at: u64,
+}
+
 pub fn to_raw(msg: CollectionKindMsg, api: &dyn Api) -> StdResult<CollectionKind> {
     let raw = match msg {
-        CollectionKindMsg::Single { image } => CollectionKind::Single { image },
+        CollectionKindMsg::Single {
+            image,
+            base_uri,
+            token_uri_suffix,
+            onchain_metadata,
+            base_uri_frozen,
+        } => CollectionKind::Single {
+            image,
+            base_uri,
+            token_uri_suffix,
+            onchain_metadata,
+            base_uri_frozen: base_uri_frozen.unwrap_or(false),
+        },
         CollectionKindMsg::Collectible {
             minter,
             public_key,
             cover,
+            key_type,
         } => CollectionKind::Collectible {
             minter: api.addr_validate(minter.as_str())?,
             cover,
             public_key,
+            key_type,
+            secondary_key: None,
         },
     };
     Ok(raw)
@@ -33,15 +62,61 @@ pub fn to_raw(msg: CollectionKindMsg, api: &dyn Api) -> StdResult<CollectionKind
 
 pub fn to_msg(raw: CollectionKind) -> CollectionKindMsg {
     match raw {
-        CollectionKind::Single { image } => CollectionKindMsg::Single { image },
+        CollectionKind::Single {
+            image,
+            base_uri,
+            token_uri_suffix,
+            onchain_metadata,
+            base_uri_frozen,
+        } => CollectionKindMsg::Single {
+            image,
+            base_uri,
+            token_uri_suffix,
+            onchain_metadata,
+            base_uri_frozen: Some(base_uri_frozen),
+        },
         CollectionKind::Collectible {
             minter,
             public_key,
             cover,
+            key_type,
+            secondary_key: _,
         } => CollectionKindMsg::Collectible {
             minter: minter.to_string(),
             public_key,
             cover,
+            key_type,
         },
     }
 }
+
+/// Returns the bot protection keys accepted at `now`, primary key first.
+pub fn active_public_keys(raw: &CollectionKind, now: u64) -> Vec<ActivePublicKey> {
+    let mut keys = vec![];
+
+    if let CollectionKind::Collectible {
+        public_key,
+        key_type,
+        secondary_key,
+        ..
+    } = raw
+    {
+        if let Some(public_key) = public_key {
+            keys.push(ActivePublicKey {
+                public_key: public_key.clone(),
+                key_type: key_type.unwrap_or(KeyType::Secp256k1),
+                expires_at: None,
+            });
+        }
+
+        if let Some(secondary_key) = secondary_key.as_ref().filter(|key| now <= key.expires_at) {
+            keys.push(ActivePublicKey {
+                public_key: secondary_key.public_key.clone(),
+                key_type: secondary_key.key_type.unwrap_or(KeyType::Secp256k1),
+                expires_at: Some(secondary_key.expires_at),
+            });
+        }
+    }
+
+    keys
+}
@@ -8,10 +8,18 @@ use crate::state::collection_kind::CollectionKind;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub owner: Addr,
+    pub pending_owner: Option<Addr>,
+    // can pause minting besides the owner
+    pub pauser: Option<Addr>,
     pub nft_address: Option<Addr>,
     pub name: String,
     pub description: String,
     pub max_token_count: u32,
+    pub max_per_user: Option<u16>,
+    // seconds after which an unfulfilled reservation can be refunded
+    pub reservation_deadline: Option<u64>,
+    // tokens held back from public stages for owner airdrops
+    pub team_reserve: Option<u32>,
     pub collection_kind: CollectionKind,
 }
 
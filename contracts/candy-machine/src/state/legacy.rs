@@ -0,0 +1,30 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Map, U32Key, U8Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// Storage layouts of previous contract versions, only used by migrations
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintStageV0_1_0 {
+    pub id: u8,
+    pub name: String,
+    pub start: Option<u64>,
+    pub finish: Option<u64>,
+    pub max_per_user: Option<u16>,
+    pub price: Option<Uint128>,
+    pub whitelist_enabled: bool,
+}
+
+pub const STAGE_V0_1_0: Map<U8Key, MintStageV0_1_0> = Map::new("stage");
+
+// v0.1.0 accepted only uusd payments
+pub const DENOM_V0_1_0: &str = "uusd";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReservationV0_1_0 {
+    pub token_id: u32,
+    pub user_address: Addr,
+}
+
+pub const RESERVATION_V0_1_0: Map<U32Key, ReservationV0_1_0> = Map::new("reservation");
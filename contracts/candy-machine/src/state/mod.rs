@@ -1,6 +1,11 @@
 pub mod collection_kind;
 pub mod config;
+pub mod legacy;
+pub mod queue;
+pub mod rebate;
 pub mod reservation;
+pub mod reveal;
+pub mod shuffle;
 pub mod stage;
 pub mod state;
 pub mod user;
@@ -0,0 +1,88 @@
+use crate::msg::ReservationPayment;
+use crate::state::reservation;
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U32Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+
+/// Mint of a hidden drop, its token ids are assigned once the drop is revealed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedMint {
+    pub owner: Addr,
+    pub quantity: u32,
+    pub stage_id: Option<u8>,
+    // price paid per token
+    pub paid: Option<ReservationPayment>,
+    pub queued_at: u64,
+}
+
+pub const QUEUE: Map<U32Key, QueuedMint> = Map::new("mint-queue");
+pub const QUEUE_NEXT_ID: Item<u32> = Item::new("mint-queue-next-id");
+
+/// Appends a mint to the queue and returns its id. The payment stays held
+/// until the mint is settled or cancelled.
+pub fn push(storage: &mut dyn Storage, mint: &QueuedMint) -> StdResult<u32> {
+    let id = QUEUE_NEXT_ID.may_load(storage)?.unwrap_or_default();
+    QUEUE.save(storage, U32Key::from(id), mint)?;
+    QUEUE_NEXT_ID.save(storage, &(id + 1))?;
+
+    if let Some(paid) = total_paid(mint)? {
+        reservation::hold_payment(storage, &paid)?;
+    }
+
+    Ok(id)
+}
+
+pub fn load(storage: &dyn Storage, queue_id: u32) -> Option<QueuedMint> {
+    QUEUE.may_load(storage, U32Key::from(queue_id)).ok()?
+}
+
+/// Removes a cancelled mint from the queue and releases its payment.
+pub fn remove(storage: &mut dyn Storage, queue_id: u32, mint: &QueuedMint) -> StdResult<()> {
+    QUEUE.remove(storage, U32Key::from(queue_id));
+    if let Some(paid) = total_paid(mint)? {
+        reservation::release_payment(storage, &paid)?;
+    }
+
+    Ok(())
+}
+
+/// Removes and returns up to `limit` queued mints, oldest first.
+pub fn take(storage: &mut dyn Storage, limit: usize) -> StdResult<Vec<QueuedMint>> {
+    let entries: Vec<(Vec<u8>, QueuedMint)> = QUEUE
+        .range(storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    let mut mints = Vec::with_capacity(entries.len());
+    for (key, mint) in entries {
+        QUEUE.remove(
+            storage,
+            U32Key::from(u32::from_be_bytes(key.try_into().unwrap())),
+        );
+        if let Some(paid) = total_paid(&mint)? {
+            reservation::release_payment(storage, &paid)?;
+        }
+        mints.push(mint);
+    }
+
+    Ok(mints)
+}
+
+fn total_paid(mint: &QueuedMint) -> StdResult<Option<ReservationPayment>> {
+    let quantity = Uint128::from(mint.quantity);
+    Ok(match &mint.paid {
+        Some(ReservationPayment::Native(coin)) => {
+            let mut coin = coin.clone();
+            coin.amount = coin.amount.checked_mul(quantity)?;
+            Some(ReservationPayment::Native(coin))
+        }
+        Some(ReservationPayment::Cw20(coin)) => {
+            let mut coin = coin.clone();
+            coin.amount = coin.amount.checked_mul(quantity)?;
+            Some(ReservationPayment::Cw20(coin))
+        }
+        None => None,
+    })
+}
@@ -0,0 +1,61 @@
+use crate::msg::ReservationPayment;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Map, U8Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Purchase {
+    pub quantity: u32,
+    // total paid for `quantity` tokens, lowered to the clearing price once the rebate is claimed
+    pub paid: ReservationPayment,
+}
+
+/// Auction sales of a stage that were not refunded, `held` is the part of `paid`
+/// kept back from withdrawals for the rebates.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Sales {
+    pub quantity: u32,
+    pub paid: ReservationPayment,
+    pub held: Uint128,
+}
+
+pub const PURCHASE: Map<(&[u8], U8Key), Purchase> = Map::new("auction-purchase");
+pub const SALES: Map<U8Key, Sales> = Map::new("auction-sales");
+// price of the latest auction sale, the clearing price once the stage is settled
+pub const LAST_PRICE: Map<U8Key, Uint128> = Map::new("auction-last-price");
+
+pub fn store_purchase(
+    storage: &mut dyn Storage,
+    stage_id: u8,
+    owner: &Addr,
+    purchase: &Purchase,
+) -> StdResult<()> {
+    PURCHASE.save(storage, (owner.as_bytes(), U8Key::from(stage_id)), purchase)
+}
+
+pub fn remove_purchase(storage: &mut dyn Storage, stage_id: u8, owner: &Addr) {
+    PURCHASE.remove(storage, (owner.as_bytes(), U8Key::from(stage_id)))
+}
+
+pub fn load_purchase(storage: &dyn Storage, stage_id: u8, owner: &Addr) -> Option<Purchase> {
+    PURCHASE
+        .may_load(storage, (owner.as_bytes(), U8Key::from(stage_id)))
+        .ok()?
+}
+
+pub fn store_last_price(storage: &mut dyn Storage, stage_id: u8, price: Uint128) -> StdResult<()> {
+    LAST_PRICE.save(storage, U8Key::from(stage_id), &price)
+}
+
+pub fn load_last_price(storage: &dyn Storage, stage_id: u8) -> Option<Uint128> {
+    LAST_PRICE.may_load(storage, U8Key::from(stage_id)).ok()?
+}
+
+pub fn store_sales(storage: &mut dyn Storage, stage_id: u8, sales: &Sales) -> StdResult<()> {
+    SALES.save(storage, U8Key::from(stage_id), sales)
+}
+
+pub fn load_sales(storage: &dyn Storage, stage_id: u8) -> Option<Sales> {
+    SALES.may_load(storage, U8Key::from(stage_id)).ok()?
+}
@@ -1,16 +1,33 @@
-use cosmwasm_std::{Addr, StdResult, Storage};
-use cw_storage_plus::{Map, U32Key};
+use crate::msg::{ReservationPayment, ReservationStatus};
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Map, U32Key, U64Key};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Reservation {
     pub token_id: u32,
     pub user_address: Addr,
+    pub status: ReservationStatus,
+    // mint stage the token was reserved in, None for airdrops and migrated reservations
+    #[serde(default)]
+    pub stage_id: Option<u8>,
+    #[serde(default)]
+    pub airdropped: bool,
+    pub paid: Option<ReservationPayment>,
+    pub reserved_at: u64,
 }
 
 pub const RESERVATION: Map<U32Key, Reservation> = Map::new("reservation");
 pub const UNPROCESSED: Map<U32Key, Addr> = Map::new("unprocessed");
+pub const USED_NONCE: Map<U64Key, bool> = Map::new("used-nonce");
+// token ids of refunded reservations, handed out again before new ids
+pub const RELEASED: Map<U32Key, bool> = Map::new("released");
+// payments of pending reservations by denom and cw20 address, kept back from
+// withdrawals so refunds can always be paid
+pub const HELD_NATIVE: Map<&str, Uint128> = Map::new("held-native");
+pub const HELD_CW20: Map<&str, Uint128> = Map::new("held-cw20");
 
 pub fn store(storage: &mut dyn Storage, token_id: u32, reservation: &Reservation) -> StdResult<()> {
     RESERVATION.save(storage, U32Key::from(token_id), &reservation)
@@ -35,3 +52,86 @@ pub fn store_unprocessed(
 pub fn remove_unprocessed(storage: &mut dyn Storage, token_id: u32) {
     UNPROCESSED.remove(storage, U32Key::from(token_id))
 }
+
+pub fn store_used_nonce(storage: &mut dyn Storage, nonce: u64) -> StdResult<()> {
+    USED_NONCE.save(storage, U64Key::from(nonce), &true)
+}
+
+pub fn is_nonce_used(storage: &dyn Storage, nonce: u64) -> bool {
+    USED_NONCE
+        .may_load(storage, U64Key::from(nonce))
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+pub fn store_released(storage: &mut dyn Storage, token_id: u32) -> StdResult<()> {
+    RELEASED.save(storage, U32Key::from(token_id), &true)
+}
+
+pub fn released_count(storage: &dyn Storage) -> u32 {
+    RELEASED.keys(storage, None, None, Order::Ascending).count() as u32
+}
+
+/// Removes and returns up to `limit` released token ids, lowest first.
+pub fn take_released(storage: &mut dyn Storage, limit: usize) -> Vec<u32> {
+    let token_ids: Vec<u32> = RELEASED
+        .keys(storage, None, None, Order::Ascending)
+        .take(limit)
+        .map(|key| u32::from_be_bytes(key.try_into().unwrap()))
+        .collect();
+
+    for token_id in token_ids.iter() {
+        RELEASED.remove(storage, U32Key::from(*token_id));
+    }
+
+    token_ids
+}
+
+pub fn hold_payment(storage: &mut dyn Storage, payment: &ReservationPayment) -> StdResult<()> {
+    update_held(storage, payment, |held, amount| {
+        Ok(held.checked_add(amount)?)
+    })
+}
+
+pub fn release_payment(storage: &mut dyn Storage, payment: &ReservationPayment) -> StdResult<()> {
+    update_held(storage, payment, |held, amount| {
+        Ok(held.saturating_sub(amount))
+    })
+}
+
+pub fn held_native(storage: &dyn Storage, denom: &str) -> Uint128 {
+    HELD_NATIVE
+        .may_load(storage, denom)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+pub fn held_cw20(storage: &dyn Storage, address: &str) -> Uint128 {
+    HELD_CW20
+        .may_load(storage, address)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn update_held<A>(
+    storage: &mut dyn Storage,
+    payment: &ReservationPayment,
+    action: A,
+) -> StdResult<()>
+where
+    A: FnOnce(Uint128, Uint128) -> StdResult<Uint128>,
+{
+    match payment {
+        ReservationPayment::Native(coin) => HELD_NATIVE.update(storage, &coin.denom, |held| {
+            action(held.unwrap_or_default(), coin.amount)
+        }),
+        ReservationPayment::Cw20(coin) => HELD_CW20.update(storage, &coin.address, |held| {
+            action(held.unwrap_or_default(), coin.amount)
+        }),
+    }?;
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Reveal {
+    pub base_uri_hash: String,
+    pub base_uri: Option<String>,
+}
+
+pub const REVEAL: Item<Reveal> = Item::new("reveal");
@@ -0,0 +1,36 @@
+use cosmwasm_std::{Binary, StdResult, Storage};
+use cw_storage_plus::{Item, Map, U32Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Shuffle {
+    pub seed_hash: String,
+    // set from the revealed seed and updated on every draw
+    pub entropy: Option<Binary>,
+}
+
+pub const SHUFFLE: Item<Shuffle> = Item::new("shuffle");
+// sparse Fisher-Yates pool, position `i` holds token id `i + 1` unless stored here
+pub const POOL: Map<U32Key, u32> = Map::new("shuffle-pool");
+
+fn pool_value(storage: &dyn Storage, position: u32) -> u32 {
+    POOL.may_load(storage, U32Key::from(position))
+        .ok()
+        .flatten()
+        .unwrap_or(position + 1)
+}
+
+/// Removes and returns the token id at `position` from a pool of `remaining` ids.
+pub fn draw(storage: &mut dyn Storage, position: u32, remaining: u32) -> StdResult<u32> {
+    let last = remaining - 1;
+    let token_id = pool_value(storage, position);
+
+    if position != last {
+        let last_token_id = pool_value(storage, last);
+        POOL.save(storage, U32Key::from(position), &last_token_id)?;
+    }
+    POOL.remove(storage, U32Key::from(last));
+
+    Ok(token_id)
+}
@@ -1,17 +1,38 @@
 use crate::msg::MintStage;
-use cosmwasm_std::{StdResult, Storage};
+use cosmwasm_std::{Order, StdResult, Storage};
 use cw_storage_plus::{Map, U8Key};
 
 pub const STAGE: Map<U8Key, MintStage> = Map::new("stage");
+pub const STAGE_MINTED: Map<U8Key, u32> = Map::new("stage-minted");
 
 pub fn store(storage: &mut dyn Storage, stage_id: u8, stage: &MintStage) -> StdResult<()> {
     STAGE.save(storage, U8Key::from(stage_id), &stage)
 }
 
 pub fn remove(storage: &mut dyn Storage, stage_id: u8) {
-    STAGE.remove(storage, U8Key::from(stage_id))
+    STAGE.remove(storage, U8Key::from(stage_id));
+    STAGE_MINTED.remove(storage, U8Key::from(stage_id))
 }
 
 pub fn load(storage: &dyn Storage, stage_id: u8) -> Option<MintStage> {
     STAGE.may_load(storage, U8Key::from(stage_id)).ok()?
 }
+
+pub fn load_all(storage: &dyn Storage) -> StdResult<Vec<MintStage>> {
+    STAGE
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, v)| v))
+        .collect()
+}
+
+pub fn store_minted(storage: &mut dyn Storage, stage_id: u8, minted: u32) -> StdResult<()> {
+    STAGE_MINTED.save(storage, U8Key::from(stage_id), &minted)
+}
+
+pub fn load_minted(storage: &dyn Storage, stage_id: u8) -> u32 {
+    STAGE_MINTED
+        .may_load(storage, U8Key::from(stage_id))
+        .ok()
+        .flatten()
+        .unwrap_or(0)
+}
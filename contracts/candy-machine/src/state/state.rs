@@ -6,6 +6,13 @@ use cw_storage_plus::Item;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
     pub token_count: u32,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub airdropped: u32,
+    // tokens of mints queued until the drop is revealed
+    #[serde(default)]
+    pub queued: u32,
 }
 
 pub const STATE: Item<State> = Item::new("state");
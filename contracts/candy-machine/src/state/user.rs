@@ -4,6 +4,8 @@ use cosmwasm_storage::{bucket, bucket_read, Bucket, ReadonlyBucket};
 pub static PREFIX_USER: &[u8] = b"user";
 pub static PREFIX_WHITELIST: &[u8] = b"whitelist";
 pub static PREFIX_USER_INDEX: &[u8] = b"index-user";
+pub static PREFIX_STAGE_MINTS: &[u8] = b"stage-mints";
+pub static PREFIX_STAGE_GENERATION: &[u8] = b"stage-generation";
 
 pub fn store(storage: &mut dyn Storage, owner: &CanonicalAddr, user: u16) -> StdResult<()> {
     let mut user_bucket: Bucket<u16> = bucket(storage, PREFIX_USER);
@@ -20,6 +22,24 @@ pub fn load(storage: &dyn Storage, owner: &CanonicalAddr) -> u16 {
     user_bucket.load(owner.as_slice()).unwrap_or_default()
 }
 
+pub fn store_stage(
+    storage: &mut dyn Storage,
+    stage_id: u8,
+    owner: &CanonicalAddr,
+    user: u16,
+) -> StdResult<()> {
+    let stage = stage_namespace(storage, stage_id);
+    Bucket::<u16>::multilevel(storage, &[PREFIX_USER_INDEX, PREFIX_STAGE_MINTS, &stage])
+        .save(owner.as_slice(), &user)
+}
+
+pub fn load_stage(storage: &dyn Storage, stage_id: u8, owner: &CanonicalAddr) -> u16 {
+    let stage = stage_namespace(storage, stage_id);
+    ReadonlyBucket::<u16>::multilevel(storage, &[PREFIX_USER_INDEX, PREFIX_STAGE_MINTS, &stage])
+        .load(owner.as_slice())
+        .unwrap_or_default()
+}
+
 pub fn register_whitelist(
     storage: &mut dyn Storage,
     stage_id: u8,
@@ -42,12 +62,38 @@ fn save_whitelist(
     owner: &CanonicalAddr,
     whitelisted: bool,
 ) -> StdResult<()> {
-    Bucket::<bool>::multilevel(storage, &[PREFIX_USER_INDEX, PREFIX_WHITELIST, &[stage_id]])
+    let stage = stage_namespace(storage, stage_id);
+    Bucket::<bool>::multilevel(storage, &[PREFIX_USER_INDEX, PREFIX_WHITELIST, &stage])
         .save(owner.as_slice(), &whitelisted)
 }
 
 pub fn is_whitelisted(storage: &dyn Storage, stage_id: u8, owner: &CanonicalAddr) -> bool {
-    ReadonlyBucket::<bool>::multilevel(storage, &[PREFIX_USER_INDEX, PREFIX_WHITELIST, &[stage_id]])
+    let stage = stage_namespace(storage, stage_id);
+    ReadonlyBucket::<bool>::multilevel(storage, &[PREFIX_USER_INDEX, PREFIX_WHITELIST, &stage])
         .load(owner.as_slice())
         .unwrap_or_default()
 }
+
+/// Starts a new generation of the per-user mints and the whitelist of a removed
+/// stage. The entries of the old generation are left behind instead of being
+/// removed one by one, which could run out of gas on large stages.
+pub fn remove_stage(storage: &mut dyn Storage, stage_id: u8) -> StdResult<()> {
+    let generation = load_generation(storage, stage_id);
+    bucket(storage, PREFIX_STAGE_GENERATION).save(&[stage_id], &(generation + 1))
+}
+
+fn load_generation(storage: &dyn Storage, stage_id: u8) -> u32 {
+    bucket_read(storage, PREFIX_STAGE_GENERATION)
+        .load(&[stage_id])
+        .unwrap_or_default()
+}
+
+// the first generation keeps the namespace of the stage id alone
+fn stage_namespace(storage: &dyn Storage, stage_id: u8) -> Vec<u8> {
+    let mut namespace = vec![stage_id];
+    match load_generation(storage, stage_id) {
+        0 => (),
+        generation => namespace.extend_from_slice(&generation.to_be_bytes()),
+    }
+    namespace
+}
@@ -0,0 +1,355 @@
+use crate::msg::{
+    CollectionKind, ConfigResponse, ConfigureShuffleMsg, ExecuteMsg, InstantiateMsg,
+    MintStageResponse, QueryMsg, ReservationResponse, ReservationStatus, StateResponse,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, to_binary, CosmosMsg, Response, SubMsg, Timestamp, WasmMsg};
+use cw721_metadata_onchain::{ExecuteMsg as Cw721ExecuteMsg, Metadata, MintMsg};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
+use crate::testing::instantiate;
+use crate::testing::refund;
+use crate::testing::shuffle::{self, TEST_SHUFFLE_SEED, TEST_SHUFFLE_SEED_HASH};
+use crate::testing::{
+    mock_deps, MockDeps, TEST_MINTER, TEST_NFT_ADDRESS, TEST_NFT_DESCRIPTION, TEST_NFT_IMAGE,
+    TEST_NFT_NAME, TEST_OWNER, TEST_STAGE_ID, TEST_USER_1,
+};
+
+const TEST_USER_2: &str = "terra1e8ryd9ezefuucd4mje33zdms9m2s90m57878v8";
+const TEST_USER_3: &str = "terra1e8ryd9ezefuucd4mje33zdms9m2s90m57878v7";
+
+fn exec(deps: &mut MockDeps, sender: &str, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg)
+}
+
+fn airdrop(deps: &mut MockDeps, recipients: Vec<(&str, u32)>) -> Result<Response, ContractError> {
+    let recipients = recipients
+        .into_iter()
+        .map(|(recipient, quantity)| (recipient.to_string(), quantity))
+        .collect();
+    exec(deps, TEST_OWNER, ExecuteMsg::Airdrop { recipients })
+}
+
+fn set_team_reserve(
+    deps: &mut MockDeps,
+    team_reserve: Option<u32>,
+) -> Result<Response, ContractError> {
+    exec(
+        deps,
+        TEST_OWNER,
+        ExecuteMsg::SetTeamReserve { team_reserve },
+    )
+}
+
+fn query_state(deps: &MockDeps) -> StateResponse {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    from_binary(&res).unwrap()
+}
+
+fn mint_msg(token_id: u32, owner: &str) -> SubMsg {
+    let mint_msg = MintMsg {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: Some(Metadata {
+            image: Some(TEST_NFT_IMAGE.to_string()),
+            description: Some(TEST_NFT_DESCRIPTION.into()),
+            name: Some(format!("{} #{}", TEST_NFT_NAME, token_id)),
+            attributes: None,
+            ..Metadata::default()
+        }),
+    };
+
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TEST_NFT_ADDRESS.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg)).unwrap(),
+        funds: vec![],
+    }))
+}
+
+#[test]
+fn success_airdrop_single() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    let res = airdrop(&mut deps, vec![(TEST_USER_1, 2), (TEST_USER_2, 1)]).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "airdrop"),
+            attr("recipient", TEST_USER_1),
+            attr("token_ids", "1,2"),
+            attr("recipient", TEST_USER_2),
+            attr("token_ids", "3"),
+        ]
+    );
+    assert_eq!(
+        res.messages,
+        vec![
+            mint_msg(1, TEST_USER_1),
+            mint_msg(2, TEST_USER_1),
+            mint_msg(3, TEST_USER_2),
+        ]
+    );
+
+    let state = query_state(&deps);
+    assert_eq!(state.token_count, 3);
+    assert_eq!(state.airdropped, 3);
+
+    // airdrops do not count against the per-user limits of the stages
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+}
+
+#[test]
+fn success_airdrop_collectible() {
+    let mut deps = mock_deps();
+    let init_msg = InstantiateMsg {
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+    instantiate::exec(&mut deps, init_msg);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    let res = airdrop(&mut deps, vec![(TEST_USER_1, 2)]).unwrap();
+    assert!(res.messages.is_empty());
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Reservation { token_id: 2 },
+    )
+    .unwrap();
+    let reservation: ReservationResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        reservation,
+        ReservationResponse {
+            token_id: 2,
+            owner: TEST_USER_1.to_string(),
+            status: ReservationStatus::Pending,
+            paid: None,
+            reserved_at: mock_env().block.time.seconds(),
+        }
+    );
+    assert_eq!(query_state(&deps).token_count, 2);
+}
+
+#[test]
+fn success_refund_airdrop_collectible() {
+    let mut deps = mock_deps();
+    let init_msg = InstantiateMsg {
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+    instantiate::exec(&mut deps, init_msg);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+    set_team_reserve(&mut deps, Some(2)).unwrap();
+    exec(
+        &mut deps,
+        TEST_OWNER,
+        ExecuteMsg::SetReservationDeadline {
+            reservation_deadline: Some(10),
+        },
+    )
+    .unwrap();
+
+    airdrop(&mut deps, vec![(TEST_USER_1, 2)]).unwrap();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(env.block.time.seconds() + 11);
+    let res = refund::exec(&mut deps, env, TEST_USER_1, 1).unwrap();
+    assert!(res.messages.is_empty());
+
+    // the refunded token is back in the team reserve
+    let state = query_state(&deps);
+    assert_eq!(state.token_count, 1);
+    assert_eq!(state.airdropped, 1);
+    airdrop(&mut deps, vec![(TEST_USER_2, 1)]).unwrap();
+    assert_eq!(query_state(&deps).airdropped, 2);
+}
+
+#[test]
+fn success_airdrop_shuffled() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+    let commit = ConfigureShuffleMsg::Commit {
+        seed_hash: TEST_SHUFFLE_SEED_HASH.to_string(),
+    };
+    shuffle::exec(&mut deps, TEST_OWNER, commit).unwrap();
+
+    // airdrops of a hidden drop wait in the queue like stage mints
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    let res = airdrop(&mut deps, vec![(TEST_USER_2, 1)]).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "airdrop"),
+            attr("recipient", TEST_USER_2),
+            attr("queue_id", "1"),
+        ]
+    );
+    assert!(res.messages.is_empty());
+
+    let reveal = ConfigureShuffleMsg::Reveal {
+        seed: TEST_SHUFFLE_SEED.to_string(),
+    };
+    shuffle::exec(&mut deps, TEST_OWNER, reveal).unwrap();
+
+    // the revealed draws cannot be taken ahead of the queued mints
+    match airdrop(&mut deps, vec![(TEST_USER_3, 1)]) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::QueuedMintsNotSettled {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    exec(
+        &mut deps,
+        TEST_USER_1,
+        ExecuteMsg::SettleMints { limit: None },
+    )
+    .unwrap();
+    airdrop(&mut deps, vec![(TEST_USER_3, 1)]).unwrap();
+
+    let state = query_state(&deps);
+    assert_eq!(state.token_count, 3);
+    assert_eq!(state.airdropped, 2);
+    assert_eq!(state.queued, 0);
+}
+
+#[test]
+fn success_team_reserve() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    set_team_reserve(&mut deps, Some(3)).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.team_reserve, Some(3));
+
+    // public stages can only mint the supply left outside of the reserve
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MintStage {
+            stage_id: TEST_STAGE_ID,
+        },
+    )
+    .unwrap();
+    let mint_stage: MintStageResponse = from_binary(&res).unwrap();
+    assert_eq!(mint_stage.remaining, 2);
+
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    airdrop(&mut deps, vec![(TEST_USER_1, 1)]).unwrap();
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_2, &[])).unwrap();
+
+    match candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_3, &[])) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NoMoreNftsToMint {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    // the rest of the reserve is still available to airdrops
+    airdrop(&mut deps, vec![(TEST_USER_3, 2)]).unwrap();
+    assert_eq!(query_state(&deps).token_count, 5);
+}
+
+#[test]
+fn success_airdrop_public_supply() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    set_team_reserve(&mut deps, Some(1)).unwrap();
+
+    // airdrops beyond the reserve use up the public supply
+    airdrop(&mut deps, vec![(TEST_USER_1, 4)]).unwrap();
+
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    assert_eq!(query_state(&deps).token_count, 5);
+}
+
+#[test]
+fn fail_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    let msg = ExecuteMsg::Airdrop {
+        recipients: vec![(TEST_USER_1.to_string(), 1)],
+    };
+    match exec(&mut deps, TEST_USER_1, msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Unauthorized {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    let msg = ExecuteMsg::SetTeamReserve {
+        team_reserve: Some(1),
+    };
+    match exec(&mut deps, TEST_USER_1, msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Unauthorized {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_invalid_quantity() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    for recipients in [vec![], vec![(TEST_USER_1, 1), (TEST_USER_2, 0)]] {
+        match airdrop(&mut deps, recipients) {
+            Ok(_) => panic!("Must return error"),
+            Err(ContractError::InvalidQuantity {}) => (),
+            Err(e) => panic!("Unexpected error {:?}", e),
+        }
+    }
+}
+
+#[test]
+fn fail_not_enough_nfts() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    match airdrop(&mut deps, vec![(TEST_USER_1, 3), (TEST_USER_2, 2)]) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotEnoughNftsToMint { remaining }) => assert_eq!(remaining, 4),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_invalid_team_reserve() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    match set_team_reserve(&mut deps, Some(5)) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidTeamReserve { max }) => assert_eq!(max, 4),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
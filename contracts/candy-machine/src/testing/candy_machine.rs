@@ -1,8 +1,11 @@
-use crate::msg::{ExecuteMsg, InstantiateMsg, MintStage, QueryMsg, StateResponse};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MintStage, MintStageResponse, MintStagesResponse, QueryMsg,
+    StageMints, StateResponse, UserMintsResponse,
+};
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    attr, coins, from_binary, to_binary, CosmosMsg, Env, MessageInfo, Response, SubMsg, Timestamp,
-    Uint128, WasmMsg,
+    attr, coin, coins, from_binary, to_binary, CosmosMsg, Env, MessageInfo, Response, SubMsg,
+    Timestamp, Uint128, WasmMsg,
 };
 use cw721_metadata_onchain::{ExecuteMsg as Cw721ExecuteMsg, Metadata, MintMsg};
 
@@ -23,7 +26,27 @@ pub fn exec(deps: &mut MockDeps, env: Env, info: MessageInfo) -> Result<Response
         info,
         ExecuteMsg::Mint {
             stage_id: TEST_STAGE_ID,
-            signature: None,
+            quantity: None,
+            signatures: None,
+            merkle_proof: None,
+        },
+    )
+}
+
+pub fn exec_reserve(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Reserve {
+            stage_id: TEST_STAGE_ID,
+            quantity: None,
+            signatures: None,
+            merkle_proof: None,
         },
     )
 }
@@ -44,6 +67,8 @@ fn success_mint_public_free() {
         vec![
             attr("action", "mint"),
             attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
         ]
     );
 
@@ -79,7 +104,7 @@ fn success_mint_public_with_price() {
     let mut deps = mock_deps();
     let amount = 100 * TEST_BASE_DENOM;
     let mint_stage = MintStage {
-        price: Some(Uint128::from(amount)),
+        price: Some(coin(amount, "uusd")),
         ..instantiate::default_mint_stage()
     };
 
@@ -101,6 +126,8 @@ fn success_mint_public_with_price() {
         vec![
             attr("action", "mint"),
             attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
         ]
     );
 
@@ -158,6 +185,8 @@ fn success_mint_private_free() {
         vec![
             attr("action", "mint"),
             attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
         ]
     );
 
@@ -196,7 +225,7 @@ fn success_mint_private_with_price() {
 
     let mint_stage = MintStage {
         whitelist_enabled: true,
-        price: Some(Uint128::from(amount)),
+        price: Some(coin(amount, "uusd")),
         ..instantiate::default_mint_stage()
     };
 
@@ -219,6 +248,8 @@ fn success_mint_private_with_price() {
         vec![
             attr("action", "mint"),
             attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
         ]
     );
 
@@ -249,6 +280,19 @@ fn success_mint_private_with_price() {
     assert_eq!(1, state.token_count);
 }
 
+#[test]
+fn fail_reserve_single_collection() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    match exec_reserve(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::MintRequired {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
 #[test]
 fn fail_swap_not_started() {
     let mut deps = mock_deps();
@@ -322,22 +366,14 @@ fn fail_no_more_nfts_to_mint() {
 #[test]
 fn fail_maximum_mint_amount_per_user_exceeded() {
     let mut deps = mock_deps();
-    let mint_stage = MintStage {
-        max_per_user: Some(0),
-        ..instantiate::default_mint_stage()
-    };
-
-    let init_msg = InstantiateMsg {
-        mint_stages: vec![mint_stage],
-        ..instantiate::default_msg()
-    };
-
     let env = mock_env();
 
-    instantiate::exec(&mut deps, init_msg);
+    instantiate::default(&mut deps);
 
     configure::set_nft_address(&mut deps, env.clone()).unwrap();
 
+    exec(&mut deps, env.clone(), mock_info(TEST_USER_1, &[])).unwrap();
+
     match exec(&mut deps, env, mock_info(TEST_USER_1, &[])) {
         Ok(_) => panic!("Must return error"),
         Err(ContractError::MaximumMintAmountPerUserExceeded {}) => (),
@@ -350,7 +386,7 @@ fn fail_zero_amount_not_allowed() {
     let mut deps = mock_deps();
     let amount = 100 * TEST_BASE_DENOM;
     let mint_stage = MintStage {
-        price: Some(Uint128::from(amount)),
+        price: Some(coin(amount, "uusd")),
         ..instantiate::default_mint_stage()
     };
 
@@ -377,7 +413,7 @@ fn fail_invalid_amount() {
     let mut deps = mock_deps();
     let amount = 100 * TEST_BASE_DENOM;
     let mint_stage = MintStage {
-        price: Some(Uint128::from(amount)),
+        price: Some(coin(amount, "uusd")),
         ..instantiate::default_mint_stage()
     };
 
@@ -424,3 +460,453 @@ fn fail_not_whitelisted() {
         Err(e) => panic!("Unexpected error {:?}", e),
     }
 }
+
+#[test]
+fn success_mint_public_with_custom_denom_price() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+    let mint_stage = MintStage {
+        price: Some(coin(amount, "ujuno")),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    let env = mock_env();
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, env.clone()).unwrap();
+
+    // price denom is not uusd anymore
+    match exec(
+        &mut deps,
+        env,
+        mock_info(TEST_USER_1, &coins(amount, "uusd")),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotAllowZeroAmount {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    let info = mock_info(TEST_USER_1, &coins(amount, "ujuno"));
+    let res = exec(&mut deps, mock_env(), info).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "mint"),
+            attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
+        ]
+    );
+
+    // should increase counter by 1
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(1, state.token_count);
+}
+
+#[test]
+fn fail_other_denoms_not_allowed() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+    let mint_stage = MintStage {
+        price: Some(coin(amount, "ujuno")),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    let env = mock_env();
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, env.clone()).unwrap();
+
+    let funds = vec![coin(amount, "ujuno"), coin(amount, "uusd")];
+    match exec(&mut deps, env, mock_info(TEST_USER_1, &funds)) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotAllowOtherDenoms { denom }) => assert_eq!(denom, "ujuno"),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+fn exec_stage(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+    stage_id: u8,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Mint {
+            stage_id,
+            quantity: None,
+            signatures: None,
+            merkle_proof: None,
+        },
+    )
+}
+
+#[test]
+fn success_mint_per_stage_max_per_user() {
+    let mut deps = mock_deps();
+    let public_stage = configure_mint_stage::next_mint_stage();
+    let mut public_env = mock_env();
+    public_env.block.time = Timestamp::from_seconds(public_stage.start.unwrap());
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![instantiate::default_mint_stage(), public_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    exec_stage(
+        &mut deps,
+        mock_env(),
+        mock_info(TEST_USER_1, &[]),
+        TEST_STAGE_ID,
+    )
+    .unwrap();
+
+    // minting in first stage does not count against the second one
+    exec_stage(
+        &mut deps,
+        public_env.clone(),
+        mock_info(TEST_USER_1, &[]),
+        2,
+    )
+    .unwrap();
+
+    match exec_stage(
+        &mut deps,
+        mock_env(),
+        mock_info(TEST_USER_1, &[]),
+        TEST_STAGE_ID,
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::MaximumMintAmountPerUserExceeded {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::UserMints {
+            address: TEST_USER_1.to_string(),
+        },
+    )
+    .unwrap();
+    let value: UserMintsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        value,
+        UserMintsResponse {
+            stages: vec![
+                StageMints {
+                    stage_id: TEST_STAGE_ID,
+                    count: 1
+                },
+                StageMints {
+                    stage_id: 2,
+                    count: 1
+                },
+            ],
+            total: 2,
+        }
+    );
+}
+
+#[test]
+fn fail_global_maximum_mint_amount_per_user_exceeded() {
+    let mut deps = mock_deps();
+    let public_stage = configure_mint_stage::next_mint_stage();
+    let mut public_env = mock_env();
+    public_env.block.time = Timestamp::from_seconds(public_stage.start.unwrap());
+
+    let init_msg = InstantiateMsg {
+        max_per_user: Some(1),
+        mint_stages: vec![instantiate::default_mint_stage(), public_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    exec_stage(
+        &mut deps,
+        mock_env(),
+        mock_info(TEST_USER_1, &[]),
+        TEST_STAGE_ID,
+    )
+    .unwrap();
+
+    match exec_stage(
+        &mut deps,
+        public_env.clone(),
+        mock_info(TEST_USER_1, &[]),
+        2,
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::MaximumMintAmountPerUserExceeded {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+fn exec_quantity(
+    deps: &mut MockDeps,
+    info: MessageInfo,
+    quantity: u16,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Mint {
+            stage_id: TEST_STAGE_ID,
+            quantity: Some(quantity),
+            signatures: None,
+            merkle_proof: None,
+        },
+    )
+}
+
+#[test]
+fn success_mint_multiple_with_price() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+    let mint_stage = MintStage {
+        price: Some(coin(amount, "uusd")),
+        max_per_user: Some(3),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    let info = mock_info(TEST_USER_1, &coins(3 * amount, "uusd"));
+    let res = exec_quantity(&mut deps, info, 3).unwrap();
+
+    let expected_messages: Vec<SubMsg> = (1..=3)
+        .map(|token_id| {
+            let mint_msg = MintMsg {
+                token_id: token_id.to_string(),
+                owner: TEST_USER_1.to_string(),
+                token_uri: None,
+                extension: Some(Metadata {
+                    image: Some(TEST_NFT_IMAGE.to_string()),
+                    description: Some(TEST_NFT_DESCRIPTION.into()),
+                    name: Some(format!("{} #{}", TEST_NFT_NAME, token_id)),
+                    attributes: None,
+                    ..Metadata::default()
+                }),
+            };
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: TEST_NFT_ADDRESS.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg)).unwrap(),
+                funds: vec![],
+            }))
+        })
+        .collect();
+    assert_eq!(res.messages, expected_messages);
+
+    // should increase counter by 3
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(3, state.token_count);
+}
+
+#[test]
+fn fail_mint_multiple_invalid_amount() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+    let mint_stage = MintStage {
+        price: Some(coin(amount, "uusd")),
+        max_per_user: Some(3),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    let info = mock_info(TEST_USER_1, &coins(amount, "uusd"));
+    match exec_quantity(&mut deps, info, 2) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidAmount { amount: expected }) => {
+            assert_eq!(expected, Uint128::from(2 * amount))
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_mint_multiple_not_enough_nfts() {
+    let mut deps = mock_deps();
+    let mint_stage = MintStage {
+        max_per_user: None,
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 3).unwrap();
+
+    match exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 3) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotEnoughNftsToMint { remaining }) => assert_eq!(remaining, 2),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_mint_multiple_maximum_mint_amount_per_user_exceeded() {
+    let mut deps = mock_deps();
+    let mint_stage = MintStage {
+        max_per_user: Some(2),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    match exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 3) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::MaximumMintAmountPerUserExceeded {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+fn instantiate_with_stage_max_supply(deps: &mut MockDeps, max_supply: u32) {
+    let mint_stage = MintStage {
+        max_per_user: None,
+        max_supply: Some(max_supply),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(deps, init_msg);
+
+    configure::set_nft_address(deps, mock_env()).unwrap();
+}
+
+fn query_mint_stage(deps: &MockDeps) -> MintStageResponse {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MintStage {
+            stage_id: TEST_STAGE_ID,
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn success_mint_stage_max_supply() {
+    let mut deps = mock_deps();
+    instantiate_with_stage_max_supply(&mut deps, 3);
+
+    let value = query_mint_stage(&deps);
+    assert_eq!((value.minted, value.remaining), (0, 3));
+
+    exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 2).unwrap();
+
+    let value = query_mint_stage(&deps);
+    assert_eq!((value.minted, value.remaining), (2, 1));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MintStages { limit: None },
+    )
+    .unwrap();
+    let value: MintStagesResponse = from_binary(&res).unwrap();
+    assert_eq!(value.mint_stages, vec![query_mint_stage(&deps)]);
+
+    match exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 2) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotEnoughNftsToMint { remaining }) => assert_eq!(remaining, 1),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 1).unwrap();
+
+    match exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 1) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::MintStageSoldOut { id }) => assert_eq!(id, TEST_STAGE_ID),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    // the rest of the collection is left for other stages
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.token_count, 3);
+}
+
+#[test]
+fn success_mint_stage_remaining_limited_by_collection() {
+    let mut deps = mock_deps();
+    // more than the 5 tokens of the collection
+    instantiate_with_stage_max_supply(&mut deps, 10);
+
+    exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 4).unwrap();
+
+    let value = query_mint_stage(&deps);
+    assert_eq!((value.minted, value.remaining), (4, 1));
+}
+
+#[test]
+fn fail_mint_zero_quantity() {
+    let mut deps = mock_deps();
+
+    instantiate::default(&mut deps);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    match exec_quantity(&mut deps, mock_info(TEST_USER_1, &[]), 0) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidQuantity {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
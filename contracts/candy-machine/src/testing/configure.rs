@@ -15,6 +15,7 @@ pub fn set_nft_address_msg() -> ExecuteMsg {
         description: None,
         name: None,
         nft_address: Some(TEST_NFT_ADDRESS.to_string()),
+        max_per_user: None,
     }
 }
 
@@ -39,6 +40,7 @@ fn fail_configure_unauthorized() {
             description: None,
             name: Some(new_nft_name),
             nft_address: None,
+            max_per_user: None,
         },
     );
 
@@ -65,6 +67,7 @@ fn success_configure() {
             description: None,
             nft_address: Some(new_nft_name.clone()),
             name: Some(new_nft_name.clone()),
+            max_per_user: Some(3),
         },
     )
     .unwrap();
@@ -82,12 +85,25 @@ fn success_configure() {
     assert_eq!(
         value,
         ConfigResponse {
+            owner: TEST_OWNER.to_string(),
+            pending_owner: None,
+            pauser: None,
             name: new_nft_name.clone(),
             description: TEST_NFT_DESCRIPTION.to_string(),
             collection_kind: CollectionKind::Single {
-                image: TEST_NFT_IMAGE.to_string()
+                image: TEST_NFT_IMAGE.to_string(),
+                base_uri: None,
+                token_uri_suffix: None,
+                onchain_metadata: None,
+                base_uri_frozen: Some(false),
             },
             max_token_count: 5,
+            max_per_user: Some(3),
+            reservation_deadline: None,
+            team_reserve: None,
+            active_public_keys: vec![],
+            shuffle: None,
+            reveal: None,
             nft_address: Some(new_nft_name)
         }
     )
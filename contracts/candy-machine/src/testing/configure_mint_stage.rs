@@ -1,9 +1,14 @@
-use crate::msg::{ConfigureMintStageMsg, ExecuteMsg, IsWhitelistedResponse, MintStage, QueryMsg};
+use crate::msg::{
+    ConfigureMintStageMsg, ExecuteMsg, IsWhitelistedResponse, MintStage, MintStageResponse,
+    MintStagesResponse, QueryMsg,
+};
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{attr, from_binary, Env, Response};
+use cosmwasm_std::{attr, coin, from_binary, Env, Response, Timestamp};
 
 use crate::contract::{execute, query};
 use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
 use crate::testing::instantiate;
 use crate::testing::{mock_deps, MockDeps, TEST_OWNER, TEST_STAGE_ID, TEST_USER_1};
 
@@ -44,6 +49,7 @@ fn success_update_whitelist() {
         QueryMsg::IsWhitelisted {
             stage_id: TEST_STAGE_ID,
             address: TEST_USER_1.to_string(),
+            merkle_proof: None,
         },
     )
     .unwrap();
@@ -71,8 +77,10 @@ fn success_update_mint_stage() {
                 start: None,
                 finish: None,
                 max_per_user: Some(10),
-                price: None,
+                price: Some(coin(100, "ujuno")),
+                cw20_price: None,
                 whitelist_enabled: None,
+                max_supply: None,
             },
         },
     )
@@ -94,17 +102,21 @@ fn success_update_mint_stage() {
         },
     )
     .unwrap();
-    let value: MintStage = from_binary(&res).unwrap();
+    let value: MintStageResponse = from_binary(&res).unwrap();
     assert_eq!(
-        value,
+        value.stage,
         MintStage {
             id: mint_stage.id,
             name: mint_stage.name,
             start: mint_stage.start,
             finish: mint_stage.finish,
             max_per_user: Some(10),
-            price: mint_stage.price,
-            whitelist_enabled: mint_stage.whitelist_enabled
+            price: Some(coin(100, "ujuno")),
+            cw20_price: mint_stage.cw20_price,
+            whitelist_enabled: mint_stage.whitelist_enabled,
+            merkle_root: mint_stage.merkle_root,
+            max_supply: mint_stage.max_supply,
+            dutch_auction: mint_stage.dutch_auction,
         }
     )
 }
@@ -147,3 +159,281 @@ fn fail_unknown_mint_stage() {
         e => panic!("unexpected error: {:?}", e),
     }
 }
+
+#[test]
+fn fail_update_mint_stage_invalid_time() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let default_stage = instantiate::default_mint_stage();
+
+    let info = mock_info(TEST_OWNER, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ConfigureMintStage {
+            id: TEST_STAGE_ID,
+            config: ConfigureMintStageMsg::Config {
+                name: None,
+                start: None,
+                finish: default_stage.start.map(|start| start - 1),
+                max_per_user: None,
+                price: None,
+                cw20_price: None,
+                whitelist_enabled: None,
+                max_supply: None,
+            },
+        },
+    );
+
+    match res.unwrap_err() {
+        ContractError::InvalidMintStageTime { .. } => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+pub fn next_mint_stage() -> MintStage {
+    let default_stage = instantiate::default_mint_stage();
+
+    MintStage {
+        id: 2,
+        name: "Next".to_string(),
+        start: Some(default_stage.finish.unwrap() + 1),
+        finish: Some(default_stage.finish.unwrap() + 100),
+        ..default_stage
+    }
+}
+
+pub fn add_mint_stage(deps: &mut MockDeps, stage: MintStage) -> Result<Response, ContractError> {
+    let info = mock_info(TEST_OWNER, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::AddMintStage { stage },
+    )
+}
+
+#[test]
+fn success_add_mint_stage_mid_sale() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    // mint in the first stage
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    let next_stage = next_mint_stage();
+    let res = add_mint_stage(&mut deps, next_stage.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "add_mint_stage"),
+            attr("sender", TEST_OWNER.to_string()),
+            attr("stage_id", "2"),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MintStages { limit: None },
+    )
+    .unwrap();
+    let value: MintStagesResponse = from_binary(&res).unwrap();
+    let mint_stages: Vec<MintStage> = value
+        .mint_stages
+        .into_iter()
+        .map(|item| item.stage)
+        .collect();
+    assert_eq!(
+        mint_stages,
+        vec![instantiate::default_mint_stage(), next_stage.clone()]
+    );
+
+    // mint in the added stage
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(next_stage.start.unwrap());
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(TEST_USER_1, &[]),
+        ExecuteMsg::Mint {
+            stage_id: next_stage.id,
+            quantity: None,
+            signatures: None,
+            merkle_proof: None,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn success_remove_mint_stage() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    let info = mock_info(TEST_OWNER, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::RemoveMintStage { id: TEST_STAGE_ID },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "remove_mint_stage"),
+            attr("sender", TEST_OWNER.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+        ]
+    );
+
+    match candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::UnknownMintStage {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn success_readd_removed_mint_stage() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    update_whitelist(&mut deps, mock_env()).unwrap();
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    let info = mock_info(TEST_OWNER, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::RemoveMintStage { id: TEST_STAGE_ID },
+    )
+    .unwrap();
+    add_mint_stage(&mut deps, instantiate::default_mint_stage()).unwrap();
+
+    // the mints of the removed stage do not count against the new one
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::IsWhitelisted {
+            stage_id: TEST_STAGE_ID,
+            address: TEST_USER_1.to_string(),
+            merkle_proof: None,
+        },
+    )
+    .unwrap();
+    let value: IsWhitelistedResponse = from_binary(&res).unwrap();
+    assert_eq!(value, IsWhitelistedResponse { whitelisted: false })
+}
+
+#[test]
+fn fail_add_mint_stage_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let info = mock_info(TEST_USER_1, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::AddMintStage {
+            stage: next_mint_stage(),
+        },
+    );
+
+    match res.unwrap_err() {
+        ContractError::Unauthorized {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_add_mint_stage_already_exists() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let stage = MintStage {
+        id: TEST_STAGE_ID,
+        ..next_mint_stage()
+    };
+
+    match add_mint_stage(&mut deps, stage).unwrap_err() {
+        ContractError::MintStageAlreadyExists { id } => assert_eq!(id, TEST_STAGE_ID),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_add_mint_stage_invalid_time() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let next_stage = next_mint_stage();
+    let stage = MintStage {
+        start: next_stage.finish,
+        finish: next_stage.start,
+        ..next_stage
+    };
+
+    match add_mint_stage(&mut deps, stage).unwrap_err() {
+        ContractError::InvalidMintStageTime { .. } => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_add_mint_stage_overlap() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let default_stage = instantiate::default_mint_stage();
+    let stage = MintStage {
+        start: default_stage.finish,
+        ..next_mint_stage()
+    };
+
+    match add_mint_stage(&mut deps, stage).unwrap_err() {
+        ContractError::MintStageOverlap { id } => assert_eq!(id, TEST_STAGE_ID),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    // open ended stage overlaps with every other stage
+    let stage = MintStage {
+        start: None,
+        finish: None,
+        ..next_mint_stage()
+    };
+
+    match add_mint_stage(&mut deps, stage).unwrap_err() {
+        ContractError::MintStageOverlap { id } => assert_eq!(id, TEST_STAGE_ID),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_remove_unknown_mint_stage() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let info = mock_info(TEST_OWNER, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::RemoveMintStage { id: 2 },
+    );
+
+    match res.unwrap_err() {
+        ContractError::UnknownMintStage {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
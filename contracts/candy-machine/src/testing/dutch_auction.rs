@@ -0,0 +1,227 @@
+use crate::msg::{
+    ConfigureMintStageMsg, CurrentPriceResponse, DutchAuction, ExecuteMsg, InstantiateMsg,
+    MintStage, QueryMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coin, coins, from_binary, Env, Response, Timestamp, Uint128};
+use cw20::Cw20Coin;
+
+use crate::contract::{self, execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
+use crate::testing::instantiate;
+use crate::testing::{
+    mock_deps, MockDeps, TEST_CW20_ADDRESS, TEST_OWNER, TEST_STAGE_ID, TEST_USER_1,
+};
+
+const TEST_START_PRICE: u128 = 1000;
+const TEST_FLOOR_PRICE: u128 = 200;
+
+pub fn auction_stage() -> MintStage {
+    MintStage {
+        price: Some(coin(TEST_START_PRICE, "uusd")),
+        dutch_auction: Some(DutchAuction {
+            floor_price: Uint128::from(TEST_FLOOR_PRICE),
+            steps: 4,
+        }),
+        ..instantiate::default_mint_stage()
+    }
+}
+
+pub fn instantiate_with_stage(deps: &mut MockDeps, mint_stage: MintStage) {
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(deps, init_msg);
+
+    configure::set_nft_address(deps, mock_env()).unwrap();
+}
+
+// the default stage runs for 100 seconds from the mock block time
+pub fn env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(env.block.time.seconds() + seconds);
+    env
+}
+
+fn query_current_price(deps: &MockDeps, env: Env) -> CurrentPriceResponse {
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::CurrentPrice {
+            stage_id: TEST_STAGE_ID,
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+fn configure_dutch_auction(
+    deps: &mut MockDeps,
+    dutch_auction: Option<DutchAuction>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::ConfigureMintStage {
+            id: TEST_STAGE_ID,
+            config: ConfigureMintStageMsg::DutchAuction { dutch_auction },
+        },
+    )
+}
+
+#[test]
+fn success_price_decays_in_steps() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(&mut deps, auction_stage());
+
+    for (seconds, amount) in [
+        (0, 1000),
+        (24, 1000),
+        (25, 800),
+        (50, 600),
+        (99, 400),
+        (100, 200),
+        (1000, 200),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            query_current_price(&deps, env_after(*seconds)),
+            CurrentPriceResponse {
+                price: Some(coin(*amount, "uusd")),
+                cw20_price: None,
+            }
+        );
+    }
+}
+
+#[test]
+fn success_cw20_price_decays() {
+    let mut deps = mock_deps();
+    let mint_stage = MintStage {
+        price: None,
+        cw20_price: Some(Cw20Coin {
+            address: TEST_CW20_ADDRESS.to_string(),
+            amount: Uint128::from(TEST_START_PRICE),
+        }),
+        ..auction_stage()
+    };
+    instantiate_with_stage(&mut deps, mint_stage);
+
+    assert_eq!(
+        query_current_price(&deps, env_after(75)),
+        CurrentPriceResponse {
+            price: None,
+            cw20_price: Some(Cw20Coin {
+                address: TEST_CW20_ADDRESS.to_string(),
+                amount: Uint128::from(400u128),
+            }),
+        }
+    );
+}
+
+#[test]
+fn success_mint_at_current_price() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(&mut deps, auction_stage());
+
+    let info = mock_info(TEST_USER_1, &coins(TEST_START_PRICE, "uusd"));
+    match candy_machine::exec(&mut deps, env_after(50), info) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidAmount { amount }) => assert_eq!(amount, Uint128::from(600u128)),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    let info = mock_info(TEST_USER_1, &coins(600, "uusd"));
+    candy_machine::exec(&mut deps, env_after(50), info).unwrap();
+}
+
+#[test]
+fn success_configure_dutch_auction() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(
+        &mut deps,
+        MintStage {
+            dutch_auction: None,
+            ..auction_stage()
+        },
+    );
+
+    assert_eq!(
+        query_current_price(&deps, env_after(50)).price,
+        Some(coin(TEST_START_PRICE, "uusd"))
+    );
+
+    configure_dutch_auction(&mut deps, auction_stage().dutch_auction).unwrap();
+    assert_eq!(
+        query_current_price(&deps, env_after(50)).price,
+        Some(coin(600, "uusd"))
+    );
+
+    configure_dutch_auction(&mut deps, None).unwrap();
+    assert_eq!(
+        query_current_price(&deps, env_after(50)).price,
+        Some(coin(TEST_START_PRICE, "uusd"))
+    );
+}
+
+#[test]
+fn fail_invalid_dutch_auction() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(
+        &mut deps,
+        MintStage {
+            dutch_auction: None,
+            ..auction_stage()
+        },
+    );
+
+    let invalid = [
+        DutchAuction {
+            floor_price: Uint128::from(TEST_FLOOR_PRICE),
+            steps: 0,
+        },
+        DutchAuction {
+            floor_price: Uint128::zero(),
+            steps: 4,
+        },
+        DutchAuction {
+            floor_price: Uint128::from(TEST_START_PRICE),
+            steps: 4,
+        },
+    ];
+    for dutch_auction in invalid.iter() {
+        match configure_dutch_auction(&mut deps, Some(dutch_auction.clone())).unwrap_err() {
+            ContractError::InvalidDutchAuction { id } => assert_eq!(id, TEST_STAGE_ID),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+}
+
+#[test]
+fn fail_dutch_auction_without_finish() {
+    let mut deps = mock_deps();
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![MintStage {
+            finish: None,
+            ..auction_stage()
+        }],
+        ..instantiate::default_msg()
+    };
+
+    let res = contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        init_msg,
+    );
+    match res.unwrap_err() {
+        ContractError::InvalidDutchAuction { id } => assert_eq!(id, TEST_STAGE_ID),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
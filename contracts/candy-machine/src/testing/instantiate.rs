@@ -4,13 +4,14 @@ use crate::msg::{
 };
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, to_binary, Api, ContractResult, Env, MessageInfo, Reply, ReplyOn, Response,
+    coin, from_binary, to_binary, Api, ContractResult, Env, MessageInfo, Reply, ReplyOn, Response,
     SubMsg, SubMsgExecutionResponse, WasmMsg,
 };
 use cw2::{get_contract_version, ContractVersion};
 use cw721_metadata_onchain::InstantiateMsg as NftInstantiateMsg;
 
 use crate::contract::{instantiate, query, reply, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::ContractError;
 use crate::state::collection_kind::CollectionKind;
 use crate::state::config::{Config, CONFIG};
 use crate::testing::{
@@ -41,6 +42,10 @@ pub fn default_mint_stage() -> MintStage {
         max_per_user: Some(1),
         whitelist_enabled: false,
         price: None,
+        cw20_price: None,
+        merkle_root: None,
+        max_supply: None,
+        dutch_auction: None,
     }
 }
 
@@ -51,8 +56,13 @@ pub fn default_msg() -> InstantiateMsg {
         description: TEST_NFT_DESCRIPTION.to_string(),
         collection_kind: CollectionKindMsg::Single {
             image: TEST_NFT_IMAGE.to_string(),
+            base_uri: None,
+            token_uri_suffix: None,
+            onchain_metadata: None,
+            base_uri_frozen: None,
         },
         max_token_count: 5,
+        max_per_user: None,
         nft_code_id: 10u64,
         mint_stages: vec![default_mint_stage()],
     }
@@ -112,12 +122,21 @@ fn proper_initialization() {
         CONFIG.load(deps.as_ref().storage).unwrap(),
         Config {
             owner: deps.api.addr_validate(TEST_OWNER).unwrap(),
+            pending_owner: None,
+            pauser: None,
             name: TEST_NFT_NAME.to_string(),
             description: TEST_NFT_DESCRIPTION.to_string(),
             collection_kind: CollectionKind::Single {
-                image: TEST_NFT_IMAGE.to_string()
+                image: TEST_NFT_IMAGE.to_string(),
+                base_uri: None,
+                token_uri_suffix: None,
+                onchain_metadata: None,
+                base_uri_frozen: false,
             },
             max_token_count: 5,
+            max_per_user: None,
+            reservation_deadline: None,
+            team_reserve: None,
             nft_address: Some(deps.api.addr_validate("nft000").unwrap()),
         }
     );
@@ -125,7 +144,15 @@ fn proper_initialization() {
     // check state
     let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
     let value: StateResponse = from_binary(&res).unwrap();
-    assert_eq!(value, StateResponse { token_count: 0 });
+    assert_eq!(
+        value,
+        StateResponse {
+            token_count: 0,
+            paused: false,
+            airdropped: 0,
+            queued: 0,
+        }
+    );
 
     // check mint stages
     let res = query(
@@ -135,10 +162,111 @@ fn proper_initialization() {
     )
     .unwrap();
     let value: MintStagesResponse = from_binary(&res).unwrap();
-    assert_eq!(
-        value,
-        MintStagesResponse {
-            mint_stages: vec![default_mint_stage()]
+    let mint_stages: Vec<MintStage> = value
+        .mint_stages
+        .into_iter()
+        .map(|item| item.stage)
+        .collect();
+    assert_eq!(mint_stages, vec![default_mint_stage()]);
+}
+
+fn exec_with_stages(
+    deps: &mut MockDeps,
+    mint_stages: Vec<MintStage>,
+) -> Result<Response, ContractError> {
+    let msg = InstantiateMsg {
+        mint_stages,
+        ..default_msg()
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info(TEST_OWNER, &[]), msg)
+}
+
+#[test]
+fn fail_duplicate_mint_stage_id() {
+    let mut deps = mock_deps();
+    let stage = default_mint_stage();
+    let duplicate = MintStage {
+        start: stage.finish.map(|finish| finish + 1),
+        finish: stage.finish.map(|finish| finish + 100),
+        ..default_mint_stage()
+    };
+
+    match exec_with_stages(&mut deps, vec![stage, duplicate]).unwrap_err() {
+        ContractError::MintStageAlreadyExists { id } => assert_eq!(id, 1),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_invalid_mint_stage_time() {
+    let mut deps = mock_deps();
+    let stage = MintStage {
+        start: Some(200),
+        finish: Some(100),
+        ..default_mint_stage()
+    };
+
+    match exec_with_stages(&mut deps, vec![stage]).unwrap_err() {
+        ContractError::InvalidMintStageTime { start, finish } => {
+            assert_eq!(start, 200);
+            assert_eq!(finish, 100);
         }
-    );
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_overlapping_mint_stages() {
+    let mut deps = mock_deps();
+    let stage = MintStage {
+        id: 2,
+        ..default_mint_stage()
+    };
+
+    match exec_with_stages(&mut deps, vec![default_mint_stage(), stage]).unwrap_err() {
+        ContractError::MintStageOverlap { id } => assert_eq!(id, 1),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_zero_max_per_user() {
+    let mut deps = mock_deps();
+    let stage = MintStage {
+        max_per_user: Some(0),
+        ..default_mint_stage()
+    };
+
+    match exec_with_stages(&mut deps, vec![stage]).unwrap_err() {
+        ContractError::InvalidMintStageMaxPerUser { id } => assert_eq!(id, 1),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_zero_price() {
+    let mut deps = mock_deps();
+    let stage = MintStage {
+        price: Some(coin(0, "uusd")),
+        ..default_mint_stage()
+    };
+
+    match exec_with_stages(&mut deps, vec![stage]).unwrap_err() {
+        ContractError::InvalidMintStagePrice { id } => assert_eq!(id, 1),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_zero_max_supply() {
+    let mut deps = mock_deps();
+    let stage = MintStage {
+        max_supply: Some(0),
+        ..default_mint_stage()
+    };
+
+    match exec_with_stages(&mut deps, vec![stage]).unwrap_err() {
+        ContractError::InvalidMintStageMaxSupply { id } => assert_eq!(id, 1),
+        e => panic!("unexpected error: {:?}", e),
+    }
 }
@@ -0,0 +1,294 @@
+use crate::msg::{
+    ConfigureMintStageMsg, ExecuteMsg, InstantiateMsg, IsWhitelistedResponse, MerkleProof,
+    MintStage, MintStageResponse, QueryMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, Response};
+use sha2::{Digest, Sha256};
+
+use crate::contract::{self, execute, query};
+use crate::error::ContractError;
+use crate::testing::configure;
+use crate::testing::instantiate;
+use crate::testing::{mock_deps, MockDeps, TEST_OWNER, TEST_STAGE_ID, TEST_USER_1};
+
+const TEST_OTHER_LEAF: &str = "wasm1e8ryd9ezefuucd4mje33zdms9m2s90m57878v4";
+
+fn leaf(address: &str, allowance: Option<u16>) -> Vec<u8> {
+    match allowance {
+        Some(allowance) => Sha256::digest(format!("{}:{}", address, allowance).as_bytes()).to_vec(),
+        None => Sha256::digest(address.as_bytes()).to_vec(),
+    }
+}
+
+fn hash_pair(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    match a <= b {
+        true => {
+            hasher.input(a);
+            hasher.input(b);
+        }
+        false => {
+            hasher.input(b);
+            hasher.input(a);
+        }
+    }
+    hasher.result().to_vec()
+}
+
+// two leaf tree with TEST_USER_1 and one other address, returns root and proof
+fn merkle_tree(allowance: Option<u16>) -> (String, MerkleProof) {
+    let user_leaf = leaf(TEST_USER_1, allowance);
+    let other_leaf = leaf(TEST_OTHER_LEAF, None);
+
+    (
+        base64::encode(hash_pair(&user_leaf, &other_leaf)),
+        MerkleProof {
+            proof: vec![base64::encode(other_leaf)],
+            allowance,
+        },
+    )
+}
+
+fn instantiate_with_merkle_root(deps: &mut MockDeps, merkle_root: String) {
+    let mint_stage = MintStage {
+        max_per_user: Some(5),
+        whitelist_enabled: true,
+        merkle_root: Some(merkle_root),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(deps, init_msg);
+
+    configure::set_nft_address(deps, mock_env()).unwrap();
+}
+
+fn exec(
+    deps: &mut MockDeps,
+    sender: &str,
+    merkle_proof: Option<MerkleProof>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::Mint {
+            stage_id: TEST_STAGE_ID,
+            quantity: None,
+            signatures: None,
+            merkle_proof,
+        },
+    )
+}
+
+fn query_whitelisted(deps: &MockDeps, address: &str, merkle_proof: Option<MerkleProof>) -> bool {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::IsWhitelisted {
+            stage_id: TEST_STAGE_ID,
+            address: address.to_string(),
+            merkle_proof,
+        },
+    )
+    .unwrap();
+    let value: IsWhitelistedResponse = from_binary(&res).unwrap();
+    value.whitelisted
+}
+
+#[test]
+fn success_mint_with_merkle_proof() {
+    let mut deps = mock_deps();
+    let (merkle_root, merkle_proof) = merkle_tree(None);
+
+    instantiate_with_merkle_root(&mut deps, merkle_root);
+
+    let res = exec(&mut deps, TEST_USER_1, Some(merkle_proof)).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "mint"),
+            attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
+        ]
+    );
+}
+
+#[test]
+fn success_is_whitelisted_with_merkle_proof() {
+    let mut deps = mock_deps();
+    let (merkle_root, merkle_proof) = merkle_tree(Some(2));
+
+    instantiate_with_merkle_root(&mut deps, merkle_root);
+
+    assert!(query_whitelisted(
+        &deps,
+        TEST_USER_1,
+        Some(merkle_proof.clone())
+    ));
+    assert!(!query_whitelisted(&deps, TEST_USER_1, None));
+    assert!(!query_whitelisted(&deps, TEST_OWNER, Some(merkle_proof)));
+}
+
+#[test]
+fn fail_merkle_allowance_exceeded() {
+    let mut deps = mock_deps();
+    let (merkle_root, merkle_proof) = merkle_tree(Some(2));
+
+    instantiate_with_merkle_root(&mut deps, merkle_root);
+
+    exec(&mut deps, TEST_USER_1, Some(merkle_proof.clone())).unwrap();
+    exec(&mut deps, TEST_USER_1, Some(merkle_proof.clone())).unwrap();
+
+    match exec(&mut deps, TEST_USER_1, Some(merkle_proof)) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::MaximumMintAmountPerUserExceeded {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_mint_with_wrong_allowance() {
+    let mut deps = mock_deps();
+    let (merkle_root, merkle_proof) = merkle_tree(Some(2));
+
+    instantiate_with_merkle_root(&mut deps, merkle_root);
+
+    let merkle_proof = MerkleProof {
+        allowance: Some(5),
+        ..merkle_proof
+    };
+
+    match exec(&mut deps, TEST_USER_1, Some(merkle_proof)) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotAllowNonWhitelisted { address }) => {
+            assert_eq!(address, TEST_USER_1)
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_mint_with_other_address_proof() {
+    let mut deps = mock_deps();
+    let (merkle_root, merkle_proof) = merkle_tree(None);
+
+    instantiate_with_merkle_root(&mut deps, merkle_root);
+
+    match exec(&mut deps, TEST_OWNER, Some(merkle_proof)) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NotAllowNonWhitelisted { address }) => assert_eq!(address, TEST_OWNER),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_mint_with_malformed_proof() {
+    let mut deps = mock_deps();
+    let (merkle_root, _) = merkle_tree(None);
+
+    instantiate_with_merkle_root(&mut deps, merkle_root);
+
+    let merkle_proof = MerkleProof {
+        proof: vec!["not a hash".to_string()],
+        allowance: None,
+    };
+
+    match exec(&mut deps, TEST_USER_1, Some(merkle_proof)) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidMerkleProof {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn success_update_merkle_root() {
+    let mut deps = mock_deps();
+    let (merkle_root, merkle_proof) = merkle_tree(None);
+
+    instantiate::default(&mut deps);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::ConfigureMintStage {
+            id: TEST_STAGE_ID,
+            config: ConfigureMintStageMsg::MerkleRoot {
+                merkle_root: Some(merkle_root),
+            },
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "update_merkle_root"),
+            attr("sender", TEST_OWNER.to_string()),
+        ]
+    );
+
+    assert!(query_whitelisted(&deps, TEST_USER_1, Some(merkle_proof)));
+
+    // the root enables the whitelist of the stage
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MintStage {
+            stage_id: TEST_STAGE_ID,
+        },
+    )
+    .unwrap();
+    let value: MintStageResponse = from_binary(&res).unwrap();
+    assert!(value.stage.whitelist_enabled);
+}
+
+#[test]
+fn fail_merkle_root_whitelist_disabled() {
+    let mut deps = mock_deps();
+    let (merkle_root, _) = merkle_tree(None);
+    let stage = MintStage {
+        merkle_root: Some(merkle_root),
+        whitelist_enabled: false,
+        ..instantiate::default_mint_stage()
+    };
+    let msg = InstantiateMsg {
+        mint_stages: vec![stage],
+        ..instantiate::default_msg()
+    };
+
+    match contract::instantiate(deps.as_mut(), mock_env(), mock_info(TEST_OWNER, &[]), msg) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::MerkleRootWhitelistDisabled { id }) => assert_eq!(id, TEST_STAGE_ID),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_update_invalid_merkle_root() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::ConfigureMintStage {
+            id: TEST_STAGE_ID,
+            config: ConfigureMintStageMsg::MerkleRoot {
+                merkle_root: Some(base64::encode("too short")),
+            },
+        },
+    );
+
+    match res.unwrap_err() {
+        ContractError::InvalidMerkleRoot { id } => assert_eq!(id, TEST_STAGE_ID),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
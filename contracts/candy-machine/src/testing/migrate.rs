@@ -0,0 +1,277 @@
+use crate::msg::{
+    CollectionKind, ConfigResponse, ConfigureMintStageMsg, ExecuteMsg, MigrateMsg, MintStage,
+    MintStagesResponse, QueryMsg, ReservationResponse, ReservationStatus,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, coin, from_binary, Addr, Uint128};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_storage_plus::{U32Key, U8Key};
+
+use crate::contract::{execute, migrate, query, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::ContractError;
+use crate::state::legacy::{MintStageV0_1_0, ReservationV0_1_0, RESERVATION_V0_1_0, STAGE_V0_1_0};
+use crate::state::reservation;
+use crate::testing::instantiate;
+use crate::testing::{
+    mock_deps, MockDeps, TEST_BASE_DENOM, TEST_NFT_DESCRIPTION, TEST_NFT_IMAGE, TEST_NFT_NAME,
+    TEST_OWNER, TEST_USER_1,
+};
+
+fn store_legacy_stage(deps: &mut MockDeps, stage: MintStageV0_1_0) {
+    STAGE_V0_1_0
+        .save(deps.as_mut().storage, U8Key::from(stage.id), &stage)
+        .unwrap();
+}
+
+#[test]
+fn success_migrate_from_0_1_0() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    // rewrite state with the v0.1.0 layout
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+    let legacy_config = format!(
+        r#"{{"owner":"{}","nft_address":null,"name":"{}","description":"{}","max_token_count":5,"collection_kind":{{"Single":{{"image":"{}"}}}}}}"#,
+        TEST_OWNER, TEST_NFT_NAME, TEST_NFT_DESCRIPTION, TEST_NFT_IMAGE
+    );
+    deps.as_mut()
+        .storage
+        .set(b"config", legacy_config.as_bytes());
+
+    let default_stage = instantiate::default_mint_stage();
+    store_legacy_stage(
+        &mut deps,
+        MintStageV0_1_0 {
+            id: default_stage.id,
+            name: default_stage.name.clone(),
+            start: default_stage.start,
+            finish: default_stage.finish,
+            max_per_user: default_stage.max_per_user,
+            price: Some(Uint128::from(100 * TEST_BASE_DENOM)),
+            whitelist_enabled: default_stage.whitelist_enabled,
+        },
+    );
+    store_legacy_stage(
+        &mut deps,
+        MintStageV0_1_0 {
+            id: 2,
+            name: "Free".to_string(),
+            start: None,
+            finish: None,
+            max_per_user: None,
+            price: None,
+            whitelist_enabled: true,
+        },
+    );
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate"),
+            attr("previous_version", "0.1.0"),
+            attr("new_version", CONTRACT_VERSION),
+        ]
+    );
+
+    // check contract version
+    assert_eq!(
+        get_contract_version(deps.as_ref().storage).unwrap(),
+        ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: CONTRACT_VERSION.to_string()
+        }
+    );
+
+    // check config
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let value: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        value,
+        ConfigResponse {
+            owner: TEST_OWNER.to_string(),
+            pending_owner: None,
+            pauser: None,
+            nft_address: None,
+            name: TEST_NFT_NAME.to_string(),
+            description: TEST_NFT_DESCRIPTION.to_string(),
+            collection_kind: CollectionKind::Single {
+                image: TEST_NFT_IMAGE.to_string(),
+                base_uri: None,
+                token_uri_suffix: None,
+                onchain_metadata: None,
+                base_uri_frozen: Some(false),
+            },
+            max_token_count: 5,
+            max_per_user: None,
+            reservation_deadline: None,
+            team_reserve: None,
+            active_public_keys: vec![],
+            shuffle: None,
+            reveal: None,
+        }
+    );
+
+    // check mint stages
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MintStages { limit: None },
+    )
+    .unwrap();
+    let value: MintStagesResponse = from_binary(&res).unwrap();
+    let mint_stages: Vec<MintStage> = value
+        .mint_stages
+        .into_iter()
+        .map(|item| item.stage)
+        .collect();
+    assert_eq!(
+        mint_stages,
+        vec![
+            MintStage {
+                price: Some(coin(100 * TEST_BASE_DENOM, "uusd")),
+                ..default_stage
+            },
+            MintStage {
+                id: 2,
+                name: "Free".to_string(),
+                start: None,
+                finish: None,
+                max_per_user: None,
+                price: None,
+                cw20_price: None,
+                whitelist_enabled: true,
+                merkle_root: None,
+                max_supply: None,
+                dutch_auction: None,
+            }
+        ]
+    );
+
+    // migrated stages overlapping each other stay editable
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::ConfigureMintStage {
+            id: 2,
+            config: ConfigureMintStageMsg::Config {
+                name: Some("Team".to_string()),
+                start: None,
+                finish: None,
+                price: None,
+                cw20_price: None,
+                max_per_user: Some(1),
+                whitelist_enabled: None,
+                max_supply: None,
+            },
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn success_migrate_same_version() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MintStages { limit: None },
+    )
+    .unwrap();
+    let value: MintStagesResponse = from_binary(&res).unwrap();
+    let mint_stages: Vec<MintStage> = value
+        .mint_stages
+        .into_iter()
+        .map(|item| item.stage)
+        .collect();
+    assert_eq!(mint_stages, vec![instantiate::default_mint_stage()]);
+}
+
+#[test]
+fn fail_migrate_different_contract() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    set_contract_version(deps.as_mut().storage, "crates.io:cw721-base", "0.1.0").unwrap();
+
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err() {
+        ContractError::CannotMigrate { previous_contract } => {
+            assert_eq!(previous_contract, "crates.io:cw721-base")
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_migrate_downgrade() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err() {
+        ContractError::CannotMigrateVersion {
+            previous_version,
+            new_version,
+        } => {
+            assert_eq!(previous_version, "99.0.0");
+            assert_eq!(new_version, CONTRACT_VERSION);
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn success_migrate_reservations_from_0_1_0() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+    // token 1 was already minted, token 2 is waiting for the minter
+    for token_id in [1u32, 2u32] {
+        RESERVATION_V0_1_0
+            .save(
+                deps.as_mut().storage,
+                U32Key::from(token_id),
+                &ReservationV0_1_0 {
+                    token_id,
+                    user_address: Addr::unchecked(TEST_USER_1),
+                },
+            )
+            .unwrap();
+    }
+    reservation::store_unprocessed(deps.as_mut().storage, 2, &Addr::unchecked(TEST_USER_1))
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    for (token_id, status) in [
+        (1, ReservationStatus::Minted),
+        (2, ReservationStatus::Pending),
+    ] {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Reservation { token_id },
+        )
+        .unwrap();
+        let value: ReservationResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            value,
+            ReservationResponse {
+                token_id,
+                owner: TEST_USER_1.to_string(),
+                status,
+                paid: None,
+                reserved_at: mock_env().block.time.seconds(),
+            }
+        );
+    }
+}
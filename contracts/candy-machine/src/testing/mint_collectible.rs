@@ -1,11 +1,11 @@
 use crate::msg::{
-    CollectionKind, ExecuteMsg, InstantiateMsg, MintStage, QueryMsg,
-    UnprocessedReservationsResponse,
+    CollectionKind, ExecuteMsg, InstantiateMsg, KeyType, MintStage, QueryMsg, ReservationResponse,
+    ReservationSignature, ReservationStatus, UnprocessedReservationsResponse,
 };
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    attr, coins, from_binary, to_binary, CosmosMsg, Env, MessageInfo, Response, SubMsg, Uint128,
-    VerificationError, WasmMsg,
+    attr, coin, coins, from_binary, to_binary, Addr, CosmosMsg, Env, MessageInfo, Response, SubMsg,
+    Timestamp, VerificationError, WasmMsg,
 };
 use cw721_metadata_onchain::Metadata as Cw721Metadata;
 use cw721_metadata_onchain::{ExecuteMsg as Cw721ExecuteMsg, Metadata, MintMsg};
@@ -16,8 +16,10 @@ use crate::testing::candy_machine;
 use crate::testing::configure;
 use crate::testing::instantiate;
 use crate::testing::{
-    mock_deps, MockDeps, TEST_BASE_DENOM, TEST_MINTER, TEST_NFT_ADDRESS, TEST_NFT_DESCRIPTION,
-    TEST_NFT_IMAGE, TEST_NFT_NAME, TEST_PUBLIC_KEY, TEST_SIGNATURE, TEST_STAGE_ID, TEST_USER_1,
+    mock_deps, MockDeps, TEST_BASE_DENOM, TEST_ED25519_PUBLIC_KEY, TEST_ED25519_SIGNATURE,
+    TEST_ED25519_SIGNATURE_2, TEST_MINTER, TEST_NFT_ADDRESS, TEST_NFT_DESCRIPTION, TEST_NFT_IMAGE,
+    TEST_NFT_NAME, TEST_OWNER, TEST_PUBLIC_KEY, TEST_SIGNATURE, TEST_SIGNATURE_2,
+    TEST_SIGNATURE_EXPIRES_AT, TEST_SIGNATURE_NONCE, TEST_STAGE_ID, TEST_USER_1,
 };
 
 pub fn exec(
@@ -35,12 +37,86 @@ pub fn exec(
     )
 }
 
+pub fn test_signature() -> ReservationSignature {
+    ReservationSignature {
+        signature: TEST_SIGNATURE.to_string(),
+        expires_at: TEST_SIGNATURE_EXPIRES_AT,
+        nonce: TEST_SIGNATURE_NONCE,
+    }
+}
+
+fn instantiate_with_public_key(deps: &mut MockDeps, max_per_user: u16) {
+    instantiate_with_key(deps, TEST_PUBLIC_KEY, KeyType::Secp256k1, max_per_user)
+}
+
+pub fn instantiate_with_key(
+    deps: &mut MockDeps,
+    public_key: &str,
+    key_type: KeyType,
+    max_per_user: u16,
+) {
+    let mint_stage = MintStage {
+        finish: None,
+        max_per_user: Some(max_per_user),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: Some(public_key.to_string()),
+            key_type: Some(key_type),
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(deps, init_msg);
+
+    configure::set_nft_address(deps, mock_env()).unwrap();
+}
+
+pub fn exec_signed(
+    deps: &mut MockDeps,
+    env: Env,
+    signature: ReservationSignature,
+) -> Result<Response, ContractError> {
+    exec_signed_batch(deps, env, vec![signature])
+}
+
+fn exec_signed_batch(
+    deps: &mut MockDeps,
+    env: Env,
+    signatures: Vec<ReservationSignature>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(TEST_USER_1, &[]),
+        ExecuteMsg::Reserve {
+            stage_id: TEST_STAGE_ID,
+            quantity: Some(signatures.len() as u16),
+            signatures: Some(signatures),
+            merkle_proof: None,
+        },
+    )
+}
+
+pub fn signature_with_nonce(signature: &str, nonce: u64) -> ReservationSignature {
+    ReservationSignature {
+        signature: signature.to_string(),
+        expires_at: TEST_SIGNATURE_EXPIRES_AT,
+        nonce,
+    }
+}
+
 #[test]
 fn success_mint_collectible() {
     let mut deps = mock_deps();
     let amount = 100 * TEST_BASE_DENOM;
     let mint_stage = MintStage {
-        price: Some(Uint128::from(amount)),
+        price: Some(coin(amount, "uusd")),
         ..instantiate::default_mint_stage()
     };
 
@@ -49,6 +125,7 @@ fn success_mint_collectible() {
         collection_kind: CollectionKind::Collectible {
             minter: TEST_MINTER.to_string(),
             public_key: None,
+            key_type: None,
             cover: TEST_NFT_IMAGE.to_string(),
         },
         ..instantiate::default_msg()
@@ -62,12 +139,14 @@ fn success_mint_collectible() {
 
     // reserve
     let info = mock_info(TEST_USER_1, &coins(amount, "uusd"));
-    let res = candy_machine::exec(&mut deps, mock_env(), info).unwrap();
+    let res = candy_machine::exec_reserve(&mut deps, mock_env(), info).unwrap();
     assert_eq!(
         res.attributes,
         vec![
             attr("action", "reserve"),
             attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
         ]
     );
 
@@ -144,6 +223,7 @@ fn success_reservation_with_signature() {
         collection_kind: CollectionKind::Collectible {
             minter: TEST_MINTER.to_string(),
             public_key: Some(TEST_PUBLIC_KEY.to_string()),
+            key_type: None,
             cover: TEST_NFT_IMAGE.to_string(),
         },
         ..instantiate::default_msg()
@@ -161,9 +241,11 @@ fn success_reservation_with_signature() {
         deps.as_mut(),
         env,
         info,
-        ExecuteMsg::Mint {
+        ExecuteMsg::Reserve {
             stage_id: TEST_STAGE_ID,
-            signature: Some(TEST_SIGNATURE.to_string()),
+            quantity: None,
+            signatures: Some(vec![test_signature()]),
+            merkle_proof: None,
         },
     )
     .unwrap();
@@ -173,6 +255,8 @@ fn success_reservation_with_signature() {
         vec![
             attr("action", "reserve"),
             attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
         ]
     );
 
@@ -195,7 +279,7 @@ fn fail_unauthorized() {
     let mut deps = mock_deps();
     let amount = 100 * TEST_BASE_DENOM;
     let mint_stage = MintStage {
-        price: Some(Uint128::from(amount)),
+        price: Some(coin(amount, "uusd")),
         ..instantiate::default_mint_stage()
     };
 
@@ -204,6 +288,7 @@ fn fail_unauthorized() {
         collection_kind: CollectionKind::Collectible {
             minter: TEST_MINTER.to_string(),
             public_key: None,
+            key_type: None,
             cover: TEST_NFT_IMAGE.to_string(),
         },
         ..instantiate::default_msg()
@@ -237,7 +322,7 @@ fn fail_unknown_reservation() {
     let mut deps = mock_deps();
     let amount = 100 * TEST_BASE_DENOM;
     let mint_stage = MintStage {
-        price: Some(Uint128::from(amount)),
+        price: Some(coin(amount, "uusd")),
         ..instantiate::default_mint_stage()
     };
 
@@ -246,6 +331,7 @@ fn fail_unknown_reservation() {
         collection_kind: CollectionKind::Collectible {
             minter: TEST_MINTER.to_string(),
             public_key: None,
+            key_type: None,
             cover: TEST_NFT_IMAGE.to_string(),
         },
         ..instantiate::default_msg()
@@ -311,6 +397,7 @@ fn fail_signature_not_provided() {
         collection_kind: CollectionKind::Collectible {
             minter: TEST_MINTER.to_string(),
             public_key: Some(TEST_PUBLIC_KEY.to_string()),
+            key_type: None,
             cover: TEST_NFT_IMAGE.to_string(),
         },
         ..instantiate::default_msg()
@@ -324,7 +411,7 @@ fn fail_signature_not_provided() {
 
     // mint
     let info = mock_info(TEST_USER_1, &[]);
-    let res = candy_machine::exec(&mut deps, mock_env(), info);
+    let res = candy_machine::exec_reserve(&mut deps, mock_env(), info);
 
     match res.unwrap_err() {
         ContractError::InvalidSignature {} => {}
@@ -344,6 +431,7 @@ fn fail_invalid_signature() {
         collection_kind: CollectionKind::Collectible {
             minter: TEST_MINTER.to_string(),
             public_key: Some(TEST_PUBLIC_KEY.to_string()),
+            key_type: None,
             cover: TEST_NFT_IMAGE.to_string(),
         },
         ..instantiate::default_msg()
@@ -361,9 +449,14 @@ fn fail_invalid_signature() {
         deps.as_mut(),
         env,
         info,
-        ExecuteMsg::Mint {
+        ExecuteMsg::Reserve {
             stage_id: TEST_STAGE_ID,
-            signature: Some("z7bviv/gnfbVsg9XPXGXJGMoDyxgJNKmT+q0X0pW6iQykM9vSV2oFScOydG3Wk2aQz+jY8gKpbPpWhvkUjglsg==".to_string()),
+            quantity: None,
+            signatures: Some(vec![ReservationSignature {
+                signature: "z7bviv/gnfbVsg9XPXGXJGMoDyxgJNKmT+q0X0pW6iQykM9vSV2oFScOydG3Wk2aQz+jY8gKpbPpWhvkUjglsg==".to_string(),
+                ..test_signature()
+            }]),
+            merkle_proof: None,
         },
     );
 
@@ -385,6 +478,7 @@ fn fail_invalid_signature_format() {
         collection_kind: CollectionKind::Collectible {
             minter: TEST_MINTER.to_string(),
             public_key: Some(TEST_PUBLIC_KEY.to_string()),
+            key_type: None,
             cover: TEST_NFT_IMAGE.to_string(),
         },
         ..instantiate::default_msg()
@@ -402,9 +496,14 @@ fn fail_invalid_signature_format() {
         deps.as_mut(),
         env,
         info,
-        ExecuteMsg::Mint {
+        ExecuteMsg::Reserve {
             stage_id: TEST_STAGE_ID,
-            signature: Some("".to_string()),
+            quantity: None,
+            signatures: Some(vec![ReservationSignature {
+                signature: "".to_string(),
+                ..test_signature()
+            }]),
+            merkle_proof: None,
         },
     );
 
@@ -413,3 +512,504 @@ fn fail_invalid_signature_format() {
         e => panic!("unexpected error: {:?}", e),
     }
 }
+
+#[test]
+fn success_reserve_multiple() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+    let mint_stage = MintStage {
+        price: Some(coin(amount, "uusd")),
+        max_per_user: Some(2),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+
+    let env = mock_env();
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, env.clone()).unwrap();
+
+    // reserve
+    let info = mock_info(TEST_USER_1, &coins(2 * amount, "uusd"));
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Reserve {
+            stage_id: TEST_STAGE_ID,
+            quantity: Some(2),
+            signatures: None,
+            merkle_proof: None,
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    // check unprocessed reservations
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::UnprocessedReservations {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let res: UnprocessedReservationsResponse = from_binary(&res).unwrap();
+    assert_eq!(vec![2, 1], res.reservations);
+}
+
+#[test]
+fn fail_signature_nonce_reused() {
+    let mut deps = mock_deps();
+    instantiate_with_public_key(&mut deps, 2);
+
+    exec_signed(&mut deps, mock_env(), test_signature()).unwrap();
+
+    match exec_signed(&mut deps, mock_env(), test_signature()) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NonceAlreadyUsed { nonce }) => assert_eq!(nonce, TEST_SIGNATURE_NONCE),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_signature_expired() {
+    let mut deps = mock_deps();
+    instantiate_with_public_key(&mut deps, 1);
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(TEST_SIGNATURE_EXPIRES_AT + 1);
+
+    match exec_signed(&mut deps, env, test_signature()) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::SignatureExpired { expires_at }) => {
+            assert_eq!(expires_at, TEST_SIGNATURE_EXPIRES_AT)
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_signature_tampered_expiry() {
+    let mut deps = mock_deps();
+    instantiate_with_public_key(&mut deps, 1);
+
+    let signature = ReservationSignature {
+        expires_at: TEST_SIGNATURE_EXPIRES_AT + 1000,
+        ..test_signature()
+    };
+
+    match exec_signed(&mut deps, mock_env(), signature) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSignature {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_signature_for_other_contract() {
+    let mut deps = mock_deps();
+    instantiate_with_public_key(&mut deps, 1);
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked("cosmos2othercontract");
+
+    match exec_signed(&mut deps, env, test_signature()) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSignature {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_signature_for_other_chain() {
+    let mut deps = mock_deps();
+    instantiate_with_public_key(&mut deps, 1);
+
+    let mut env = mock_env();
+    env.block.chain_id = "other-chain-1".to_string();
+
+    match exec_signed(&mut deps, env, test_signature()) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSignature {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn success_reserve_multiple_with_signatures() {
+    let mut deps = mock_deps();
+    instantiate_with_public_key(&mut deps, 2);
+
+    exec_signed_batch(
+        &mut deps,
+        mock_env(),
+        vec![
+            test_signature(),
+            signature_with_nonce(TEST_SIGNATURE_2, TEST_SIGNATURE_NONCE + 1),
+        ],
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::UnprocessedReservations {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let res: UnprocessedReservationsResponse = from_binary(&res).unwrap();
+    assert_eq!(vec![2, 1], res.reservations);
+}
+
+#[test]
+fn success_reservation_with_ed25519_signature() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_ED25519_PUBLIC_KEY, KeyType::Ed25519, 1);
+
+    let res = exec_signed(
+        &mut deps,
+        mock_env(),
+        signature_with_nonce(TEST_ED25519_SIGNATURE, TEST_SIGNATURE_NONCE),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "reserve"),
+            attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
+        ]
+    );
+}
+
+#[test]
+fn success_reserve_multiple_with_ed25519_batch() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_ED25519_PUBLIC_KEY, KeyType::Ed25519, 2);
+
+    exec_signed_batch(
+        &mut deps,
+        mock_env(),
+        vec![
+            signature_with_nonce(TEST_ED25519_SIGNATURE, TEST_SIGNATURE_NONCE),
+            signature_with_nonce(TEST_ED25519_SIGNATURE_2, TEST_SIGNATURE_NONCE + 1),
+        ],
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::UnprocessedReservations {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let res: UnprocessedReservationsResponse = from_binary(&res).unwrap();
+    assert_eq!(vec![2, 1], res.reservations);
+}
+
+#[test]
+fn fail_invalid_ed25519_batch() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_ED25519_PUBLIC_KEY, KeyType::Ed25519, 2);
+
+    // second signature was issued for another nonce
+    match exec_signed_batch(
+        &mut deps,
+        mock_env(),
+        vec![
+            signature_with_nonce(TEST_ED25519_SIGNATURE, TEST_SIGNATURE_NONCE),
+            signature_with_nonce(TEST_ED25519_SIGNATURE_2, TEST_SIGNATURE_NONCE + 2),
+        ],
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSignature {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_secp256k1_signature_with_ed25519_key() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_ED25519_PUBLIC_KEY, KeyType::Ed25519, 1);
+
+    match exec_signed(&mut deps, mock_env(), test_signature()) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSignature {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_signature_count_mismatch() {
+    let mut deps = mock_deps();
+    instantiate_with_public_key(&mut deps, 2);
+
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_USER_1, &[]),
+        ExecuteMsg::Reserve {
+            stage_id: TEST_STAGE_ID,
+            quantity: Some(2),
+            signatures: Some(vec![test_signature()]),
+            merkle_proof: None,
+        },
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSignatureCount { expected }) => assert_eq!(expected, 2),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_signature_nonce_repeated_in_batch() {
+    let mut deps = mock_deps();
+    instantiate_with_public_key(&mut deps, 2);
+
+    match exec_signed_batch(
+        &mut deps,
+        mock_env(),
+        vec![test_signature(), test_signature()],
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NonceAlreadyUsed { nonce }) => assert_eq!(nonce, TEST_SIGNATURE_NONCE),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+fn instantiate_without_key(deps: &mut MockDeps) {
+    let init_msg = InstantiateMsg {
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(deps, init_msg);
+
+    configure::set_nft_address(deps, mock_env()).unwrap();
+}
+
+#[test]
+fn fail_mint_collectible_without_reserve() {
+    let mut deps = mock_deps();
+    instantiate_without_key(&mut deps);
+
+    match candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ReserveRequired {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+fn query_reservation(deps: &MockDeps, token_id: u32) -> ReservationResponse {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Reservation { token_id },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn success_query_reservation_status() {
+    let mut deps = mock_deps();
+    instantiate_without_key(&mut deps);
+
+    candy_machine::exec_reserve(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    assert_eq!(
+        query_reservation(&deps, 1),
+        ReservationResponse {
+            token_id: 1,
+            owner: TEST_USER_1.to_string(),
+            status: ReservationStatus::Pending,
+            paid: None,
+            reserved_at: mock_env().block.time.seconds(),
+        }
+    );
+
+    let info = mock_info(TEST_MINTER, &[]);
+    exec(&mut deps, mock_env(), info, 1, Metadata::default()).unwrap();
+    assert_eq!(
+        query_reservation(&deps, 1).status,
+        ReservationStatus::Minted
+    );
+}
+
+#[test]
+fn fail_query_unknown_reservation() {
+    let mut deps = mock_deps();
+    instantiate_without_key(&mut deps);
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Reservation { token_id: 1 },
+    );
+
+    match res.unwrap_err() {
+        ContractError::UnknownReservation {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_minter_mint_already_processed() {
+    let mut deps = mock_deps();
+    instantiate_without_key(&mut deps);
+
+    candy_machine::exec_reserve(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    let info = mock_info(TEST_MINTER, &[]);
+    exec(&mut deps, mock_env(), info.clone(), 1, Metadata::default()).unwrap();
+
+    match exec(&mut deps, mock_env(), info, 1, Metadata::default()) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ReservationAlreadyProcessed { token_id }) => assert_eq!(token_id, 1),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+fn exec_batch(
+    deps: &mut MockDeps,
+    sender: &str,
+    token_ids: Vec<u32>,
+) -> Result<Response, ContractError> {
+    let items = token_ids
+        .into_iter()
+        .map(|token_id| {
+            let metadata = Cw721Metadata {
+                name: Some(format!("{} #{}", TEST_NFT_NAME, token_id)),
+                ..Metadata::default()
+            };
+            (token_id, metadata)
+        })
+        .collect();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::MinterMintBatch { items },
+    )
+}
+
+fn reserve_two(deps: &mut MockDeps) {
+    instantiate_without_key(deps);
+
+    candy_machine::exec_reserve(deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    candy_machine::exec_reserve(deps, mock_env(), mock_info(TEST_OWNER, &[])).unwrap();
+}
+
+#[test]
+fn success_minter_mint_batch() {
+    let mut deps = mock_deps();
+    reserve_two(&mut deps);
+
+    let res = exec_batch(&mut deps, TEST_MINTER, vec![1, 2]).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "minter_mint_batch"),
+            attr("token_ids", "1,2"),
+        ]
+    );
+
+    let expected_msgs: Vec<SubMsg> = vec![(1, TEST_USER_1), (2, TEST_OWNER)]
+        .into_iter()
+        .map(|(token_id, owner)| {
+            let mint_msg = MintMsg {
+                token_id: token_id.to_string(),
+                owner: owner.to_string(),
+                token_uri: None,
+                extension: Some(Metadata {
+                    name: Some(format!("{} #{}", TEST_NFT_NAME, token_id)),
+                    ..Metadata::default()
+                }),
+            };
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: TEST_NFT_ADDRESS.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg)).unwrap(),
+                funds: vec![],
+            }))
+        })
+        .collect();
+    assert_eq!(res.messages, expected_msgs);
+
+    // check unprocessed reservations
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::UnprocessedReservations {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let res: UnprocessedReservationsResponse = from_binary(&res).unwrap();
+    assert!(res.reservations.is_empty());
+
+    assert_eq!(
+        query_reservation(&deps, 2).status,
+        ReservationStatus::Minted
+    );
+}
+
+#[test]
+fn fail_minter_mint_batch_unknown_reservation() {
+    let mut deps = mock_deps();
+    reserve_two(&mut deps);
+
+    match exec_batch(&mut deps, TEST_MINTER, vec![1, 3]) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::UnknownReservation {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_minter_mint_batch_already_processed() {
+    let mut deps = mock_deps();
+    reserve_two(&mut deps);
+
+    match exec_batch(&mut deps, TEST_MINTER, vec![1, 2, 1]) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ReservationAlreadyProcessed { token_id }) => assert_eq!(token_id, 1),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_minter_mint_batch_unauthorized() {
+    let mut deps = mock_deps();
+    reserve_two(&mut deps);
+
+    match exec_batch(&mut deps, TEST_USER_1, vec![1, 2]) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Unauthorized {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
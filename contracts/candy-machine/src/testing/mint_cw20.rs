@@ -0,0 +1,238 @@
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, MintStage, QueryMsg, StateResponse};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    attr, coin, coins, from_binary, to_binary, CosmosMsg, Env, Response, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20Coin, Cw20ReceiveMsg};
+use cw721_metadata_onchain::{ExecuteMsg as Cw721ExecuteMsg, Metadata, MintMsg};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
+use crate::testing::instantiate;
+use crate::testing::{
+    mock_deps, MockDeps, TEST_BASE_DENOM, TEST_CW20_ADDRESS, TEST_NFT_ADDRESS,
+    TEST_NFT_DESCRIPTION, TEST_NFT_IMAGE, TEST_NFT_NAME, TEST_STAGE_ID, TEST_USER_1,
+};
+
+pub fn exec(
+    deps: &mut MockDeps,
+    env: Env,
+    token: &str,
+    amount: u128,
+) -> Result<Response, ContractError> {
+    let info = mock_info(token, &[]);
+    execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: TEST_USER_1.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Mint {
+                stage_id: TEST_STAGE_ID,
+                quantity: None,
+                signatures: None,
+                merkle_proof: None,
+            })
+            .unwrap(),
+        }),
+    )
+}
+
+pub fn exec_reserve(
+    deps: &mut MockDeps,
+    env: Env,
+    token: &str,
+    amount: u128,
+) -> Result<Response, ContractError> {
+    let info = mock_info(token, &[]);
+    execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: TEST_USER_1.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Reserve {
+                stage_id: TEST_STAGE_ID,
+                quantity: None,
+                signatures: None,
+                merkle_proof: None,
+            })
+            .unwrap(),
+        }),
+    )
+}
+
+fn instantiate_with_cw20_price(deps: &mut MockDeps, amount: u128) {
+    let mint_stage = MintStage {
+        cw20_price: Some(Cw20Coin {
+            address: TEST_CW20_ADDRESS.to_string(),
+            amount: Uint128::from(amount),
+        }),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(deps, init_msg);
+
+    configure::set_nft_address(deps, mock_env()).unwrap();
+}
+
+#[test]
+fn success_mint_with_cw20() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+
+    instantiate_with_cw20_price(&mut deps, amount);
+
+    let res = exec(&mut deps, mock_env(), TEST_CW20_ADDRESS, amount).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "mint"),
+            attr("owner", TEST_USER_1.to_string()),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("token_ids", "1"),
+        ]
+    );
+
+    let mint_msg = MintMsg {
+        token_id: "1".to_string(),
+        owner: TEST_USER_1.to_string(),
+        token_uri: None,
+        extension: Some(Metadata {
+            image: Some(TEST_NFT_IMAGE.to_string()),
+            description: Some(TEST_NFT_DESCRIPTION.into()),
+            name: Some(format!("{} #{}", TEST_NFT_NAME, 1)),
+            attributes: None,
+            ..Metadata::default()
+        }),
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TEST_NFT_ADDRESS.to_string(),
+            msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg)).unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // should increase counter by 1
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(1, state.token_count);
+}
+
+#[test]
+fn success_mint_with_cw20_or_native() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+    let mint_stage = MintStage {
+        max_per_user: Some(2),
+        price: Some(coin(amount, "uusd")),
+        cw20_price: Some(Cw20Coin {
+            address: TEST_CW20_ADDRESS.to_string(),
+            amount: Uint128::from(amount),
+        }),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    exec(&mut deps, mock_env(), TEST_CW20_ADDRESS, amount).unwrap();
+
+    let info = mock_info(TEST_USER_1, &coins(amount, "uusd"));
+    candy_machine::exec(&mut deps, mock_env(), info).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(2, state.token_count);
+}
+
+#[test]
+fn fail_cw20_payment_not_accepted() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+
+    instantiate_with_cw20_price(&mut deps, amount);
+
+    match exec(&mut deps, mock_env(), TEST_NFT_ADDRESS, amount) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Cw20PaymentNotAccepted { address }) => {
+            assert_eq!(address, TEST_NFT_ADDRESS)
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_cw20_payment_on_native_stage() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+    let mint_stage = MintStage {
+        price: Some(coin(amount, "uusd")),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(&mut deps, init_msg);
+
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    match exec(&mut deps, mock_env(), TEST_CW20_ADDRESS, amount) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Cw20PaymentNotAccepted { address: _ }) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_invalid_cw20_amount() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+
+    instantiate_with_cw20_price(&mut deps, amount);
+
+    match exec(&mut deps, mock_env(), TEST_CW20_ADDRESS, 100) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidAmount { amount: expected }) => {
+            assert_eq!(expected, Uint128::from(amount))
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_native_payment_not_accepted() {
+    let mut deps = mock_deps();
+    let amount = 100 * TEST_BASE_DENOM;
+
+    instantiate_with_cw20_price(&mut deps, amount);
+
+    let info = mock_info(TEST_USER_1, &coins(amount, "uusd"));
+    match candy_machine::exec(&mut deps, mock_env(), info) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NativePaymentNotAccepted { address }) => {
+            assert_eq!(address, TEST_CW20_ADDRESS)
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
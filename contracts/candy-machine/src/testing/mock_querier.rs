@@ -76,21 +76,12 @@ impl CustomMockWasmQuerier {
     fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
         match request {
             QueryRequest::Wasm(wasm_request) => match wasm_request {
-                // WasmQuery::Smart {
-                //     contract_addr: _,
-                //     msg,
-                // } => match from_binary(msg).unwrap() {
-                //     QueryMsg::Admin { address } => {
-                //         let mut weight: Option<u64> = None;
-                //         if address == TEST_OWNER {
-                //             weight = Some(1);
-                //         }
-                //         SystemResult::Ok(ContractResult::Ok(
-                //             to_binary(&AdminResponse { weight }).unwrap(),
-                //         ))
-                //     }
-                //     _ => panic!("DO NOT ENTER HERE"),
-                // },
+                WasmQuery::Smart { contract_addr, msg } => SystemResult::Ok(ContractResult::Ok(
+                    self.wasm_smart_query_handlers
+                        .get(contract_addr.as_str())
+                        .expect("wasm: smart query handler not found")(msg)
+                    .unwrap(),
+                )),
                 WasmQuery::Raw { contract_addr, key } => SystemResult::Ok(ContractResult::Ok(
                     self.wasm_raw_query_handlers
                         .get(contract_addr.as_str())
@@ -3,27 +3,52 @@ use cosmwasm_std::OwnedDeps;
 
 use crate::testing::mock_querier::{mock_dependencies, CustomMockWasmQuerier};
 
+mod airdrop;
 mod candy_machine;
 mod configure;
 mod configure_mint_stage;
+mod dutch_auction;
 mod instantiate;
+mod merkle_whitelist;
+mod migrate;
 mod mint_collectible;
+mod mint_cw20;
 mod mock_querier;
+mod ownership;
+mod pause;
+mod public_key;
+mod rebate;
+mod refund;
+mod reveal;
+mod shuffle;
+mod token_uri;
 mod withdraw_funds;
 
 const TEST_OWNER: &str = "wasm1x46rqay4d3cssq8gxxvqz8xt6nwlz4td20k38v";
 const TEST_USER_1: &str = "terra1e8ryd9ezefuucd4mje33zdms9m2s90m57878v9";
 const TEST_NFT_ADDRESS: &str = "wasm1e8ryd9ezefuucd4mje33zdms9m2s90m57878v1";
 const TEST_MINTER: &str = "wasm1e8ryd9ezefuucd4mje33zdms9m2s90m57878v2";
+const TEST_CW20_ADDRESS: &str = "wasm1e8ryd9ezefuucd4mje33zdms9m2s90m57878v3";
 const TEST_NFT_NAME: &str = "Test";
 const TEST_NFT_SYMBOL: &str = "TEST";
 const TEST_NFT_DESCRIPTION: &str = "TEST NFT";
 const TEST_NFT_IMAGE: &str = "ipfs://QmerYDeXcVhwPsrYc6pGJmzt1RZUgrfsCdfz9DUrkyDRed";
 const TEST_STAGE_ID: u8 = 1;
 const TEST_BASE_DENOM: u128 = 1_000_000u128;
-const TEST_PUBLIC_KEY: &str = "A/+Q17DEAXBGd5DGQwnqQAKIwkBRPZuy2Qi+J/oDpxxI";
+const TEST_PUBLIC_KEY: &str = "A7x3MKacvp6vVYBW8BDtFy4avYHu/ShhleOXpRCDcCVH";
+// signed for TEST_USER_1 on TEST_STAGE_ID of the mock contract and chain, the
+// second signatures use nonce TEST_SIGNATURE_NONCE + 1
 const TEST_SIGNATURE: &str =
-    "gwTKfDMZ1DVDz0CLox1vbSg3E1vuM9uNiALEdc4yw5wEIPXVQJfzR9WhvNC84X0U3xMZx5YIwjH5tzEHe5UDYA==";
+    "RGa6lGlqrUdpl5MD2paz6ld3w4Fa5EbwVB82M3mgVYMwdxiUoP7rTSYuiNcsPw0QKBubBCN6kDXtoOwWOo4IRA==";
+const TEST_SIGNATURE_2: &str =
+    "To6LcuCCqNydN8aEm/KN3QjacLZ47fUb0vJ12jiuGsZFKJ08zFAzQDNYb4trdF48TTrUNMb5p0r+4ZWN5XWZ/w==";
+const TEST_ED25519_PUBLIC_KEY: &str = "QS070N4gWW/bmG2TnPDSrm0IWeAjsQaRkbcLZmEVAvw=";
+const TEST_ED25519_SIGNATURE: &str =
+    "vUM8ZKK1PGkneBdMW1c2wV1lwkW/wUhT8D9T6e5U2aaIEKUCenzmu60Eat10VM09xt+OjO7rlQkaTL71WkYmBQ==";
+const TEST_ED25519_SIGNATURE_2: &str =
+    "jkJMnF8KVttIULVMi+wJcZorqbEr5Xapn2L6ZkfRFTJjjd/l91NO15KkCO+vYDOtNjbkD29SAPH9ek2Lr2BIAA==";
+const TEST_SIGNATURE_EXPIRES_AT: u64 = 1_571_797_519;
+const TEST_SIGNATURE_NONCE: u64 = 1;
 
 type MockDeps = OwnedDeps<MockStorage, MockApi, CustomMockWasmQuerier>;
 
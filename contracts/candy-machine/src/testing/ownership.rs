@@ -0,0 +1,179 @@
+use crate::msg::{ConfigResponse, ExecuteMsg, QueryMsg};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, Response};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::instantiate;
+use crate::testing::{mock_deps, MockDeps, TEST_MINTER, TEST_OWNER, TEST_USER_1};
+
+pub fn propose_new_owner(deps: &mut MockDeps, owner: &str) -> Result<Response, ContractError> {
+    let info = mock_info(TEST_OWNER, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ProposeNewOwner {
+            owner: owner.to_string(),
+        },
+    )
+}
+
+fn query_config(deps: &MockDeps) -> ConfigResponse {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn success_transfer_ownership() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let res = propose_new_owner(&mut deps, TEST_USER_1).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "propose_new_owner"),
+            attr("sender", TEST_OWNER.to_string()),
+            attr("pending_owner", TEST_USER_1.to_string()),
+        ]
+    );
+
+    let config = query_config(&deps);
+    assert_eq!(config.owner, TEST_OWNER.to_string());
+    assert_eq!(config.pending_owner, Some(TEST_USER_1.to_string()));
+
+    let info = mock_info(TEST_USER_1, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "accept_ownership"),
+            attr("previous_owner", TEST_OWNER.to_string()),
+            attr("owner", TEST_USER_1.to_string()),
+        ]
+    );
+
+    let config = query_config(&deps);
+    assert_eq!(config.owner, TEST_USER_1.to_string());
+    assert_eq!(config.pending_owner, None);
+
+    // previous owner lost access to owner only messages
+    match propose_new_owner(&mut deps, TEST_OWNER).unwrap_err() {
+        ContractError::Unauthorized {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn success_cancel_ownership_proposal() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    propose_new_owner(&mut deps, TEST_USER_1).unwrap();
+
+    let info = mock_info(TEST_OWNER, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CancelOwnershipProposal {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "cancel_ownership_proposal"),
+            attr("sender", TEST_OWNER.to_string()),
+        ]
+    );
+
+    let config = query_config(&deps);
+    assert_eq!(config.owner, TEST_OWNER.to_string());
+    assert_eq!(config.pending_owner, None);
+
+    // cancelled proposal can not be accepted
+    let info = mock_info(TEST_USER_1, &[]);
+    match execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap_err()
+    {
+        ContractError::NoPendingOwner {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_propose_new_owner_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let info = mock_info(TEST_USER_1, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ProposeNewOwner {
+            owner: TEST_USER_1.to_string(),
+        },
+    );
+
+    match res.unwrap_err() {
+        ContractError::Unauthorized {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_accept_ownership_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    propose_new_owner(&mut deps, TEST_USER_1).unwrap();
+
+    let info = mock_info(TEST_MINTER, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::AcceptOwnership {},
+    );
+
+    match res.unwrap_err() {
+        ContractError::Unauthorized {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    // proposal is kept
+    let config = query_config(&deps);
+    assert_eq!(config.pending_owner, Some(TEST_USER_1.to_string()));
+}
+
+#[test]
+fn fail_cancel_ownership_proposal_not_proposed() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let info = mock_info(TEST_OWNER, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::CancelOwnershipProposal {},
+    );
+
+    match res.unwrap_err() {
+        ContractError::NoPendingOwner {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
@@ -0,0 +1,164 @@
+use crate::msg::{CollectionKind, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, Response};
+use cw721_metadata_onchain::Metadata as Cw721Metadata;
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
+use crate::testing::instantiate;
+use crate::testing::mint_collectible;
+use crate::testing::{mock_deps, MockDeps, TEST_MINTER, TEST_NFT_IMAGE, TEST_OWNER, TEST_USER_1};
+
+const TEST_PAUSER: &str = "wasm1e8ryd9ezefuucd4mje33zdms9m2s90m57878v4";
+
+fn exec(deps: &mut MockDeps, sender: &str, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg)
+}
+
+fn query_paused(deps: &MockDeps) -> bool {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let value: StateResponse = from_binary(&res).unwrap();
+    value.paused
+}
+
+fn assert_paused<T>(res: Result<T, ContractError>) {
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Paused {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn success_pause_mint() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    exec(&mut deps, TEST_OWNER, ExecuteMsg::Pause {}).unwrap();
+    assert!(query_paused(&deps));
+
+    assert_paused(candy_machine::exec(
+        &mut deps,
+        mock_env(),
+        mock_info(TEST_USER_1, &[]),
+    ));
+
+    exec(&mut deps, TEST_OWNER, ExecuteMsg::Unpause {}).unwrap();
+    assert!(!query_paused(&deps));
+
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+}
+
+#[test]
+fn success_pause_reserve_and_minter_mint() {
+    let mut deps = mock_deps();
+    let init_msg = InstantiateMsg {
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+    instantiate::exec(&mut deps, init_msg);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    candy_machine::exec_reserve(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    exec(&mut deps, TEST_OWNER, ExecuteMsg::Pause {}).unwrap();
+
+    assert_paused(candy_machine::exec_reserve(
+        &mut deps,
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+    ));
+    assert_paused(mint_collectible::exec(
+        &mut deps,
+        mock_env(),
+        mock_info(TEST_MINTER, &[]),
+        1,
+        Cw721Metadata::default(),
+    ));
+    assert_paused(exec(
+        &mut deps,
+        TEST_MINTER,
+        ExecuteMsg::MinterMintBatch {
+            items: vec![(1, Cw721Metadata::default())],
+        },
+    ));
+
+    exec(&mut deps, TEST_OWNER, ExecuteMsg::Unpause {}).unwrap();
+
+    mint_collectible::exec(
+        &mut deps,
+        mock_env(),
+        mock_info(TEST_MINTER, &[]),
+        1,
+        Cw721Metadata::default(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn success_pauser_can_only_pause() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    exec(
+        &mut deps,
+        TEST_OWNER,
+        ExecuteMsg::SetPauser {
+            pauser: Some(TEST_PAUSER.to_string()),
+        },
+    )
+    .unwrap();
+
+    exec(&mut deps, TEST_PAUSER, ExecuteMsg::Pause {}).unwrap();
+    assert!(query_paused(&deps));
+
+    match exec(&mut deps, TEST_PAUSER, ExecuteMsg::Unpause {}).unwrap_err() {
+        ContractError::Unauthorized {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    // removed pauser can no longer pause
+    exec(&mut deps, TEST_OWNER, ExecuteMsg::Unpause {}).unwrap();
+    exec(
+        &mut deps,
+        TEST_OWNER,
+        ExecuteMsg::SetPauser { pauser: None },
+    )
+    .unwrap();
+
+    match exec(&mut deps, TEST_PAUSER, ExecuteMsg::Pause {}).unwrap_err() {
+        ContractError::Unauthorized {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_pause_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    match exec(&mut deps, TEST_USER_1, ExecuteMsg::Pause {}).unwrap_err() {
+        ContractError::Unauthorized {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    let res = exec(
+        &mut deps,
+        TEST_USER_1,
+        ExecuteMsg::SetPauser {
+            pauser: Some(TEST_USER_1.to_string()),
+        },
+    );
+    match res.unwrap_err() {
+        ContractError::Unauthorized {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
@@ -0,0 +1,218 @@
+use crate::msg::{
+    ActivePublicKey, ConfigResponse, ConfigurePublicKeyMsg, ExecuteMsg, KeyType, QueryMsg,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, Env, Response, Timestamp};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::instantiate;
+use crate::testing::mint_collectible::{
+    exec_signed, instantiate_with_key, signature_with_nonce, test_signature,
+};
+use crate::testing::{
+    mock_deps, MockDeps, TEST_ED25519_PUBLIC_KEY, TEST_ED25519_SIGNATURE, TEST_OWNER,
+    TEST_PUBLIC_KEY, TEST_SIGNATURE_NONCE, TEST_USER_1,
+};
+
+pub fn exec(
+    deps: &mut MockDeps,
+    sender: &str,
+    config: ConfigurePublicKeyMsg,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::ConfigurePublicKey { config },
+    )
+}
+
+fn query_active_public_keys(deps: &MockDeps, env: Env) -> Vec<ActivePublicKey> {
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    let value: ConfigResponse = from_binary(&res).unwrap();
+    value.active_public_keys
+}
+
+fn secp256k1_key() -> ActivePublicKey {
+    ActivePublicKey {
+        public_key: TEST_PUBLIC_KEY.to_string(),
+        key_type: KeyType::Secp256k1,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn success_rotate_public_key() {
+    let mut deps = mock_deps();
+    // failed mints are not reverted by the mock storage
+    instantiate_with_key(&mut deps, TEST_ED25519_PUBLIC_KEY, KeyType::Ed25519, 2);
+
+    let res = exec(
+        &mut deps,
+        TEST_OWNER,
+        ConfigurePublicKeyMsg::Rotate {
+            public_key: TEST_PUBLIC_KEY.to_string(),
+            key_type: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "configure_public_key"),
+            attr("sender", TEST_OWNER.to_string()),
+        ]
+    );
+
+    assert_eq!(
+        query_active_public_keys(&deps, mock_env()),
+        vec![secp256k1_key()]
+    );
+
+    // old key is no longer accepted
+    match exec_signed(
+        &mut deps,
+        mock_env(),
+        signature_with_nonce(TEST_ED25519_SIGNATURE, TEST_SIGNATURE_NONCE),
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSignature {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    exec_signed(&mut deps, mock_env(), test_signature()).unwrap();
+}
+
+#[test]
+fn success_secondary_public_key() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_ED25519_PUBLIC_KEY, KeyType::Ed25519, 1);
+
+    let expires_at = mock_env().block.time.seconds() + 50;
+    exec(
+        &mut deps,
+        TEST_OWNER,
+        ConfigurePublicKeyMsg::AddSecondary {
+            public_key: TEST_PUBLIC_KEY.to_string(),
+            key_type: Some(KeyType::Secp256k1),
+            expires_at,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_active_public_keys(&deps, mock_env()),
+        vec![
+            ActivePublicKey {
+                public_key: TEST_ED25519_PUBLIC_KEY.to_string(),
+                key_type: KeyType::Ed25519,
+                expires_at: None,
+            },
+            ActivePublicKey {
+                expires_at: Some(expires_at),
+                ..secp256k1_key()
+            },
+        ]
+    );
+
+    exec_signed(&mut deps, mock_env(), test_signature()).unwrap();
+}
+
+#[test]
+fn fail_expired_secondary_public_key() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_ED25519_PUBLIC_KEY, KeyType::Ed25519, 1);
+
+    let expires_at = mock_env().block.time.seconds() + 50;
+    exec(
+        &mut deps,
+        TEST_OWNER,
+        ConfigurePublicKeyMsg::AddSecondary {
+            public_key: TEST_PUBLIC_KEY.to_string(),
+            key_type: None,
+            expires_at,
+        },
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(expires_at + 1);
+
+    assert_eq!(query_active_public_keys(&deps, env.clone()).len(), 1);
+
+    match exec_signed(&mut deps, env, test_signature()) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InvalidSignature {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn success_remove_public_key() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_PUBLIC_KEY, KeyType::Secp256k1, 1);
+
+    exec(&mut deps, TEST_OWNER, ConfigurePublicKeyMsg::Remove {}).unwrap();
+
+    assert_eq!(query_active_public_keys(&deps, mock_env()), vec![]);
+
+    // signatures are no longer required
+    candy_machine::exec_reserve(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+}
+
+#[test]
+fn fail_secondary_public_key_expired_on_add() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_PUBLIC_KEY, KeyType::Secp256k1, 1);
+
+    let expires_at = mock_env().block.time.seconds();
+    let res = exec(
+        &mut deps,
+        TEST_OWNER,
+        ConfigurePublicKeyMsg::AddSecondary {
+            public_key: TEST_ED25519_PUBLIC_KEY.to_string(),
+            key_type: Some(KeyType::Ed25519),
+            expires_at,
+        },
+    );
+
+    match res.unwrap_err() {
+        ContractError::InvalidPublicKeyExpiry { expires_at: e } => assert_eq!(e, expires_at),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_configure_public_key_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate_with_key(&mut deps, TEST_PUBLIC_KEY, KeyType::Secp256k1, 1);
+
+    let res = exec(&mut deps, TEST_USER_1, ConfigurePublicKeyMsg::Remove {});
+
+    match res.unwrap_err() {
+        ContractError::Unauthorized {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_configure_public_key_single_collection() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let res = exec(
+        &mut deps,
+        TEST_OWNER,
+        ConfigurePublicKeyMsg::Rotate {
+            public_key: TEST_PUBLIC_KEY.to_string(),
+            key_type: None,
+        },
+    );
+
+    match res.unwrap_err() {
+        ContractError::InvalidCollectionKind {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
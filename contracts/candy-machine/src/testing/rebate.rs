@@ -0,0 +1,431 @@
+use crate::msg::{
+    CollectionKind, ExecuteMsg, InstantiateMsg, MintStage, PendingRebateResponse, QueryMsg,
+    ReservationPayment, StageRebate,
+};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, coin, coins, from_binary, to_binary, BankMsg, Env, Response, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20Coin, Cw20ExecuteMsg};
+
+use crate::contract::{self, execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
+use crate::testing::dutch_auction::{auction_stage, env_after, instantiate_with_stage};
+use crate::testing::instantiate;
+use crate::testing::mint_cw20;
+use crate::testing::refund;
+use crate::testing::{
+    mock_deps, MockDeps, TEST_CW20_ADDRESS, TEST_MINTER, TEST_NFT_IMAGE, TEST_OWNER, TEST_STAGE_ID,
+    TEST_USER_1,
+};
+
+const TEST_USER_2: &str = "terra1e8ryd9ezefuucd4mje33zdms9m2s90m57878v8";
+
+fn exec(deps: &mut MockDeps, env: Env, sender: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(sender, &[]),
+        ExecuteMsg::ClaimRebate {},
+    )
+}
+
+fn exec_mint(deps: &mut MockDeps, env: Env, sender: &str, quantity: u16, amount: u128) {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(sender, &coins(amount, "uusd")),
+        ExecuteMsg::Mint {
+            stage_id: TEST_STAGE_ID,
+            quantity: Some(quantity),
+            signatures: None,
+            merkle_proof: None,
+        },
+    )
+    .unwrap();
+}
+
+// user 1 reserves tokens 1 and 2 at 1000 and 800, user 2 sells out the
+// stage with token 3 at the clearing price of 600
+fn reserve_collectible_auction(deps: &mut MockDeps) {
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![MintStage {
+            max_per_user: None,
+            max_supply: Some(3),
+            ..auction_stage()
+        }],
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+    instantiate::exec(deps, init_msg);
+    configure::set_nft_address(deps, mock_env()).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::SetReservationDeadline {
+            reservation_deadline: Some(10),
+        },
+    )
+    .unwrap();
+
+    for (seconds, sender, amount) in [
+        (0, TEST_USER_1, 1000),
+        (25, TEST_USER_1, 800),
+        (50, TEST_USER_2, 600),
+    ] {
+        execute(
+            deps.as_mut(),
+            env_after(seconds),
+            mock_info(sender, &coins(amount, "uusd")),
+            ExecuteMsg::Reserve {
+                stage_id: TEST_STAGE_ID,
+                quantity: None,
+                signatures: None,
+                merkle_proof: None,
+            },
+        )
+        .unwrap();
+    }
+}
+
+fn query_rebates(deps: &MockDeps, env: Env, address: &str) -> Vec<StageRebate> {
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::PendingRebate {
+            address: address.to_string(),
+        },
+    )
+    .unwrap();
+    let value: PendingRebateResponse = from_binary(&res).unwrap();
+    value.rebates
+}
+
+fn assert_no_rebate(res: Result<Response, ContractError>) {
+    match res {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::NoRebateToClaim {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn success_claim_rebate_after_stage_end() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(
+        &mut deps,
+        MintStage {
+            max_per_user: None,
+            ..auction_stage()
+        },
+    );
+
+    exec_mint(&mut deps, env_after(0), TEST_USER_1, 2, 2000);
+    exec_mint(&mut deps, env_after(50), TEST_USER_2, 1, 600);
+
+    // the auction is still running
+    assert_eq!(
+        query_rebates(&deps, env_after(60), TEST_USER_1),
+        vec![StageRebate {
+            stage_id: TEST_STAGE_ID,
+            rebate: ReservationPayment::Native(coin(800, "uusd")),
+            claimable: false,
+        }]
+    );
+    assert_no_rebate(exec(&mut deps, env_after(60), TEST_USER_1));
+
+    // the last buyer paid the clearing price
+    assert_eq!(query_rebates(&deps, env_after(101), TEST_USER_2), vec![]);
+    assert_no_rebate(exec(&mut deps, env_after(101), TEST_USER_2));
+
+    let res = exec(&mut deps, env_after(101), TEST_USER_1).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "claim_rebate"),
+            attr("owner", TEST_USER_1),
+            attr("stage_ids", TEST_STAGE_ID.to_string()),
+        ]
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(800, "uusd"),
+        })]
+    );
+
+    assert_eq!(query_rebates(&deps, env_after(101), TEST_USER_1), vec![]);
+    assert_no_rebate(exec(&mut deps, env_after(101), TEST_USER_1));
+}
+
+#[test]
+fn success_claim_rebate_when_sold_out() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(
+        &mut deps,
+        MintStage {
+            max_supply: Some(2),
+            ..auction_stage()
+        },
+    );
+
+    exec_mint(&mut deps, env_after(0), TEST_USER_1, 1, 1000);
+    exec_mint(&mut deps, env_after(25), TEST_USER_2, 1, 800);
+
+    assert_eq!(
+        query_rebates(&deps, env_after(25), TEST_USER_1),
+        vec![StageRebate {
+            stage_id: TEST_STAGE_ID,
+            rebate: ReservationPayment::Native(coin(200, "uusd")),
+            claimable: true,
+        }]
+    );
+
+    let res = exec(&mut deps, env_after(25), TEST_USER_1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(200, "uusd"),
+        })]
+    );
+}
+
+#[test]
+fn success_claim_cw20_rebate() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(
+        &mut deps,
+        MintStage {
+            price: None,
+            cw20_price: Some(Cw20Coin {
+                address: TEST_CW20_ADDRESS.to_string(),
+                amount: Uint128::from(1000u128),
+            }),
+            max_per_user: None,
+            max_supply: Some(2),
+            ..auction_stage()
+        },
+    );
+
+    mint_cw20::exec(&mut deps, env_after(0), TEST_CW20_ADDRESS, 1000).unwrap();
+    mint_cw20::exec(&mut deps, env_after(50), TEST_CW20_ADDRESS, 600).unwrap();
+
+    let res = exec(&mut deps, env_after(50), TEST_USER_1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: TEST_CW20_ADDRESS.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_USER_1.to_string(),
+                amount: Uint128::from(400u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+}
+
+#[test]
+fn success_no_rebate_without_auction() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(
+        &mut deps,
+        MintStage {
+            dutch_auction: None,
+            ..auction_stage()
+        },
+    );
+
+    candy_machine::exec(
+        &mut deps,
+        mock_env(),
+        mock_info(TEST_USER_1, &coins(1000, "uusd")),
+    )
+    .unwrap();
+
+    assert_eq!(query_rebates(&deps, env_after(101), TEST_USER_1), vec![]);
+    assert_no_rebate(exec(&mut deps, env_after(101), TEST_USER_1));
+}
+
+#[test]
+fn fail_auction_with_two_currencies() {
+    let mut deps = mock_deps();
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![MintStage {
+            cw20_price: Some(Cw20Coin {
+                address: TEST_CW20_ADDRESS.to_string(),
+                amount: Uint128::from(1000u128),
+            }),
+            ..auction_stage()
+        }],
+        ..instantiate::default_msg()
+    };
+
+    let res = contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        init_msg,
+    );
+    match res.unwrap_err() {
+        ContractError::InvalidDutchAuction { id } => assert_eq!(id, TEST_STAGE_ID),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn success_refund_after_rebate() {
+    let mut deps = mock_deps();
+    reserve_collectible_auction(&mut deps);
+
+    let res = exec(&mut deps, env_after(60), TEST_USER_1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(600, "uusd"),
+        })]
+    );
+
+    // only the clearing price is still paid for the token
+    let res = refund::exec(&mut deps, env_after(60), TEST_USER_1, 1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(600, "uusd"),
+        })]
+    );
+
+    assert_eq!(query_rebates(&deps, env_after(101), TEST_USER_1), vec![]);
+    assert_no_rebate(exec(&mut deps, env_after(101), TEST_USER_1));
+}
+
+#[test]
+fn success_rebate_after_refund() {
+    let mut deps = mock_deps();
+    reserve_collectible_auction(&mut deps);
+
+    let res = refund::exec(&mut deps, env_after(60), TEST_USER_1, 1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(900, "uusd"),
+        })]
+    );
+
+    // the rest of the first price is paid out with the rebate of token 2,
+    // 1200 are returned in total as when the rebate is claimed first
+    assert_eq!(
+        query_rebates(&deps, env_after(101), TEST_USER_1),
+        vec![StageRebate {
+            stage_id: TEST_STAGE_ID,
+            rebate: ReservationPayment::Native(coin(300, "uusd")),
+            claimable: true,
+        }]
+    );
+    let res = exec(&mut deps, env_after(101), TEST_USER_1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(300, "uusd"),
+        })]
+    );
+
+    assert_no_rebate(exec(&mut deps, env_after(101), TEST_USER_1));
+}
+
+#[test]
+fn fail_remove_auction_stage_with_purchases() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(&mut deps, auction_stage());
+
+    exec_mint(&mut deps, env_after(0), TEST_USER_1, 1, 1000);
+
+    let res = execute(
+        deps.as_mut(),
+        env_after(101),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::RemoveMintStage { id: TEST_STAGE_ID },
+    );
+    match res.unwrap_err() {
+        ContractError::MintStageHasPurchases { id } => assert_eq!(id, TEST_STAGE_ID),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+fn withdraw(deps: &mut MockDeps, env: Env) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::WithdrawFunds {
+            recipient: TEST_OWNER.to_string(),
+            amount: None,
+            cw20_tokens: None,
+        },
+    )
+}
+
+fn assert_withdrawn(res: Response, amount: u128) {
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_OWNER.to_string(),
+            amount: coins(amount, "uusd"),
+        })]
+    );
+}
+
+#[test]
+fn success_withdraw_keeps_rebates() {
+    let mut deps = mock_deps();
+    instantiate_with_stage(
+        &mut deps,
+        MintStage {
+            max_per_user: None,
+            ..auction_stage()
+        },
+    );
+
+    exec_mint(&mut deps, env_after(0), TEST_USER_1, 2, 2000);
+    exec_mint(&mut deps, env_after(50), TEST_USER_2, 1, 600);
+
+    // while the auction runs everything above the floor price is held back
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(2600, "uusd"));
+    assert_withdrawn(withdraw(&mut deps, env_after(60)).unwrap(), 3 * 200);
+
+    // once settled only the rebates above the clearing price
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(2000, "uusd"));
+    assert_withdrawn(withdraw(&mut deps, env_after(101)).unwrap(), 1200);
+
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(800, "uusd"));
+    let res = exec(&mut deps, env_after(101), TEST_USER_1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(800, "uusd"),
+        })]
+    );
+
+    // nothing is held back after the claim
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(50, "uusd"));
+    assert_withdrawn(withdraw(&mut deps, env_after(101)).unwrap(), 50);
+}
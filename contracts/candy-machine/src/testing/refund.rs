@@ -0,0 +1,374 @@
+use crate::msg::{
+    CollectionKind, ConfigureMintStageMsg, ExecuteMsg, InstantiateMsg, MintStage,
+    MintStageResponse, QueryMsg, ReservationPayment, ReservationResponse, ReservationStatus,
+    StateResponse,
+};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, coin, coins, from_binary, to_binary, BankMsg, CosmosMsg, Env, Response, SubMsg,
+    Timestamp, Uint128, WasmMsg,
+};
+use cw20::{Cw20Coin, Cw20ExecuteMsg};
+use cw721_metadata_onchain::Metadata;
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::instantiate;
+use crate::testing::mint_collectible;
+use crate::testing::mint_cw20;
+use crate::testing::withdraw_funds;
+use crate::testing::{
+    mock_deps, MockDeps, TEST_BASE_DENOM, TEST_CW20_ADDRESS, TEST_MINTER, TEST_NFT_ADDRESS,
+    TEST_NFT_IMAGE, TEST_OWNER, TEST_STAGE_ID, TEST_USER_1,
+};
+
+const TEST_PRICE: u128 = 100 * TEST_BASE_DENOM;
+const TEST_DEADLINE: u64 = 50;
+
+fn instantiate_collectible(deps: &mut MockDeps, reservation_deadline: Option<u64>) {
+    let mint_stage = MintStage {
+        price: Some(coin(TEST_PRICE, "uusd")),
+        cw20_price: Some(Cw20Coin {
+            address: TEST_CW20_ADDRESS.to_string(),
+            amount: Uint128::from(TEST_PRICE),
+        }),
+        ..instantiate::default_mint_stage()
+    };
+
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![mint_stage],
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(deps, init_msg);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::Configure {
+            name: None,
+            description: None,
+            nft_address: Some(TEST_NFT_ADDRESS.to_string()),
+            max_per_user: None,
+        },
+    )
+    .unwrap();
+
+    set_reservation_deadline(deps, TEST_OWNER, reservation_deadline).unwrap();
+}
+
+fn set_reservation_deadline(
+    deps: &mut MockDeps,
+    sender: &str,
+    reservation_deadline: Option<u64>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::SetReservationDeadline {
+            reservation_deadline,
+        },
+    )
+}
+
+fn reserve(deps: &mut MockDeps, env: Env, sender: &str) {
+    let info = mock_info(sender, &coins(TEST_PRICE, "uusd"));
+    candy_machine::exec_reserve(deps, env, info).unwrap();
+}
+
+fn after_deadline() -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(env.block.time.seconds() + TEST_DEADLINE + 1);
+    env
+}
+
+pub fn exec(
+    deps: &mut MockDeps,
+    env: Env,
+    sender: &str,
+    token_id: u32,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(sender, &[]),
+        ExecuteMsg::ClaimRefund { token_id },
+    )
+}
+
+fn query_reservation(deps: &MockDeps, token_id: u32) -> ReservationResponse {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Reservation { token_id },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+fn query_token_count(deps: &MockDeps) -> u32 {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    state.token_count
+}
+
+#[test]
+fn success_claim_refund() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+
+    let res = exec(&mut deps, after_deadline(), TEST_USER_1, 1).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "claim_refund"),
+            attr("owner", TEST_USER_1.to_string()),
+            attr("token_id", "1"),
+            attr("amount", format!("{}uusd", TEST_PRICE)),
+        ]
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(TEST_PRICE, "uusd"),
+        }))]
+    );
+
+    assert_eq!(
+        query_reservation(&deps, 1).status,
+        ReservationStatus::Cancelled
+    );
+    assert_eq!(query_token_count(&deps), 0);
+}
+
+#[test]
+fn success_released_token_id_is_reserved_again() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+
+    exec(&mut deps, after_deadline(), TEST_USER_1, 1).unwrap();
+
+    reserve(&mut deps, after_deadline(), TEST_OWNER);
+    assert_eq!(
+        query_reservation(&deps, 1),
+        ReservationResponse {
+            token_id: 1,
+            owner: TEST_OWNER.to_string(),
+            status: ReservationStatus::Pending,
+            paid: Some(ReservationPayment::Native(coin(TEST_PRICE, "uusd"))),
+            reserved_at: after_deadline().block.time.seconds(),
+        }
+    );
+    assert_eq!(query_token_count(&deps), 1);
+}
+
+#[test]
+fn success_refund_frees_stage_supply() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::ConfigureMintStage {
+            id: TEST_STAGE_ID,
+            config: ConfigureMintStageMsg::Config {
+                name: None,
+                start: None,
+                finish: None,
+                max_per_user: None,
+                price: None,
+                cw20_price: None,
+                whitelist_enabled: None,
+                max_supply: Some(1),
+            },
+        },
+    )
+    .unwrap();
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+
+    let info = mock_info(TEST_OWNER, &coins(TEST_PRICE, "uusd"));
+    match candy_machine::exec_reserve(&mut deps, after_deadline(), info) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::MintStageSoldOut { id }) => assert_eq!(id, TEST_STAGE_ID),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    exec(&mut deps, after_deadline(), TEST_USER_1, 1).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MintStage {
+            stage_id: TEST_STAGE_ID,
+        },
+    )
+    .unwrap();
+    let mint_stage: MintStageResponse = from_binary(&res).unwrap();
+    assert_eq!(mint_stage.minted, 0);
+
+    // the refunded token can be reserved again in the stage
+    reserve(&mut deps, after_deadline(), TEST_OWNER);
+    assert_eq!(query_token_count(&deps), 1);
+}
+
+#[test]
+fn success_claim_cw20_refund() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    mint_cw20::exec_reserve(&mut deps, mock_env(), TEST_CW20_ADDRESS, TEST_PRICE).unwrap();
+
+    let res = exec(&mut deps, after_deadline(), TEST_USER_1, 1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TEST_CW20_ADDRESS.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_USER_1.to_string(),
+                amount: Uint128::from(TEST_PRICE),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+#[test]
+fn fail_claim_refund_before_deadline() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+
+    match exec(&mut deps, mock_env(), TEST_USER_1, 1) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ReservationDeadlineNotReached { deadline }) => {
+            assert_eq!(deadline, mock_env().block.time.seconds() + TEST_DEADLINE)
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_claim_refund_disabled() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, None);
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+
+    match exec(&mut deps, after_deadline(), TEST_USER_1, 1) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::RefundsDisabled {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_claim_refund_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+
+    match exec(&mut deps, after_deadline(), TEST_OWNER, 1) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Unauthorized {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_claim_refund_already_minted() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+
+    let info = mock_info(TEST_MINTER, &[]);
+    mint_collectible::exec(&mut deps, mock_env(), info, 1, Metadata::default()).unwrap();
+
+    match exec(&mut deps, after_deadline(), TEST_USER_1, 1) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ReservationAlreadyProcessed { token_id }) => assert_eq!(token_id, 1),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn success_clear_reservation_deadline() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+
+    match set_reservation_deadline(&mut deps, TEST_USER_1, None) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::Unauthorized {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    set_reservation_deadline(&mut deps, TEST_OWNER, None).unwrap();
+
+    match exec(&mut deps, after_deadline(), TEST_USER_1, 1) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::RefundsDisabled {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn success_withdraw_keeps_pending_payments() {
+    let mut deps = mock_deps();
+    instantiate_collectible(&mut deps, Some(TEST_DEADLINE));
+    reserve(&mut deps, mock_env(), TEST_USER_1);
+    reserve(&mut deps, mock_env(), TEST_OWNER);
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(2 * TEST_PRICE + 500, "uusd"));
+
+    // the payment of a processed reservation can be withdrawn
+    let info = mock_info(TEST_MINTER, &[]);
+    mint_collectible::exec(&mut deps, mock_env(), info, 2, Metadata::default()).unwrap();
+
+    match withdraw_funds::exec(
+        &mut deps,
+        TEST_OWNER,
+        Some(coins(TEST_PRICE + 501, "uusd")),
+        None,
+    ) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::InsufficientFunds { available, .. }) => {
+            assert_eq!(available, Uint128::from(TEST_PRICE + 500))
+        }
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+
+    let res = withdraw_funds::exec(&mut deps, TEST_OWNER, None, None).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: TEST_OWNER.to_string(),
+            amount: coins(TEST_PRICE + 500, "uusd"),
+        }))]
+    );
+
+    // the pending reservation can still be refunded, releasing its payment
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(TEST_PRICE, "uusd"));
+    exec(&mut deps, after_deadline(), TEST_USER_1, 1).unwrap();
+
+    let res = withdraw_funds::exec(&mut deps, TEST_OWNER, None, None).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: TEST_OWNER.to_string(),
+            amount: coins(TEST_PRICE, "uusd"),
+        }))]
+    );
+}
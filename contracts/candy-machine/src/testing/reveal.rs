@@ -0,0 +1,305 @@
+use crate::msg::{
+    CollectionKind, ConfigResponse, ExecuteMsg, InstantiateMsg, MintStage, QueryMsg,
+    RevealResponse, StateResponse,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    attr, coin, coins, from_binary, to_binary, BankMsg, CosmosMsg, Response, SubMsg, Timestamp,
+    WasmMsg,
+};
+use cw721_metadata_onchain::{ExecuteMsg as Cw721ExecuteMsg, Metadata, MintMsg};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
+use crate::testing::instantiate;
+use crate::testing::{
+    mock_deps, MockDeps, TEST_MINTER, TEST_NFT_ADDRESS, TEST_NFT_DESCRIPTION, TEST_NFT_IMAGE,
+    TEST_NFT_NAME, TEST_OWNER, TEST_USER_1,
+};
+
+const TEST_BASE_URI: &str = "ipfs://QmRevealedMetadata/";
+// base64 encoded sha256 of TEST_BASE_URI
+const TEST_BASE_URI_HASH: &str = "84Fgee7yA+TxaFKR4ZRRjEsA152M3lChRxqNfIBZjpA=";
+
+fn commit(deps: &mut MockDeps, sender: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::CommitReveal {
+            base_uri_hash: TEST_BASE_URI_HASH.to_string(),
+        },
+    )
+}
+
+fn reveal(deps: &mut MockDeps, sender: &str, base_uri: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::Reveal {
+            base_uri: base_uri.to_string(),
+        },
+    )
+}
+
+fn query_reveal(deps: &MockDeps) -> Option<RevealResponse> {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let value: ConfigResponse = from_binary(&res).unwrap();
+    value.reveal
+}
+
+fn expected_mint(token_uri: Option<String>) -> Vec<SubMsg> {
+    let mint_msg = MintMsg {
+        token_id: "1".to_string(),
+        owner: TEST_USER_1.to_string(),
+        token_uri,
+        extension: Some(Metadata {
+            image: Some(TEST_NFT_IMAGE.to_string()),
+            description: Some(TEST_NFT_DESCRIPTION.into()),
+            name: Some(format!("{} #{}", TEST_NFT_NAME, 1)),
+            attributes: None,
+            ..Metadata::default()
+        }),
+    };
+
+    vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TEST_NFT_ADDRESS.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg)).unwrap(),
+        funds: vec![],
+    }))]
+}
+
+fn settle(deps: &mut MockDeps) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_USER_1, &[]),
+        ExecuteMsg::SettleMints { limit: None },
+    )
+}
+
+#[test]
+fn success_queue_mints_until_reveal() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    commit(&mut deps, TEST_OWNER).unwrap();
+    assert_eq!(
+        query_reveal(&deps),
+        Some(RevealResponse {
+            base_uri_hash: TEST_BASE_URI_HASH.to_string(),
+            base_uri: None,
+        })
+    );
+
+    // nothing is minted before the final metadata is known
+    let res = candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    assert!(res.messages.is_empty());
+
+    match settle(&mut deps).unwrap_err() {
+        ContractError::DropNotRevealed {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    let res = reveal(&mut deps, TEST_OWNER, TEST_BASE_URI).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "reveal"),
+            attr("sender", TEST_OWNER),
+            attr("base_uri", TEST_BASE_URI),
+        ]
+    );
+    assert_eq!(
+        query_reveal(&deps),
+        Some(RevealResponse {
+            base_uri_hash: TEST_BASE_URI_HASH.to_string(),
+            base_uri: Some(TEST_BASE_URI.to_string()),
+        })
+    );
+
+    let res = settle(&mut deps).unwrap();
+    assert_eq!(
+        res.messages,
+        expected_mint(Some(format!("{}{}", TEST_BASE_URI, 1)))
+    );
+}
+
+fn cancel(deps: &mut MockDeps, seconds: u64) -> Result<Response, ContractError> {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(env.block.time.seconds() + seconds);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(TEST_USER_1, &[]),
+        ExecuteMsg::CancelQueuedMint { queue_id: 0 },
+    )
+}
+
+fn instantiate_queued(deps: &mut MockDeps) {
+    let init_msg = InstantiateMsg {
+        mint_stages: vec![MintStage {
+            price: Some(coin(100, "uusd")),
+            ..instantiate::default_mint_stage()
+        }],
+        ..instantiate::default_msg()
+    };
+    instantiate::exec(deps, init_msg);
+    configure::set_nft_address(deps, mock_env()).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_OWNER, &[]),
+        ExecuteMsg::SetReservationDeadline {
+            reservation_deadline: Some(10),
+        },
+    )
+    .unwrap();
+
+    commit(deps, TEST_OWNER).unwrap();
+    candy_machine::exec(
+        deps,
+        mock_env(),
+        mock_info(TEST_USER_1, &coins(100, "uusd")),
+    )
+    .unwrap();
+}
+
+#[test]
+fn success_cancel_queued_mint() {
+    let mut deps = mock_deps();
+    instantiate_queued(&mut deps);
+
+    match cancel(&mut deps, 10).unwrap_err() {
+        ContractError::ReservationDeadlineNotReached { deadline } => {
+            assert_eq!(deadline, mock_env().block.time.seconds() + 10)
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    // the owner never revealed, the payment goes back to the buyer
+    let res = cancel(&mut deps, 11).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "cancel_queued_mint"),
+            attr("owner", TEST_USER_1),
+            attr("queue_id", "0"),
+            attr("amount", "100uusd"),
+        ]
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: TEST_USER_1.to_string(),
+            amount: coins(100, "uusd"),
+        })]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.token_count, 0);
+    assert_eq!(state.queued, 0);
+
+    match cancel(&mut deps, 11).unwrap_err() {
+        ContractError::UnknownQueuedMint {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_cancel_revealed_queued_mint() {
+    let mut deps = mock_deps();
+    instantiate_queued(&mut deps);
+
+    reveal(&mut deps, TEST_OWNER, TEST_BASE_URI).unwrap();
+
+    match cancel(&mut deps, 11).unwrap_err() {
+        ContractError::DropAlreadyRevealed {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn success_mint_after_reveal_sets_token_uri() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    commit(&mut deps, TEST_OWNER).unwrap();
+    reveal(&mut deps, TEST_OWNER, TEST_BASE_URI).unwrap();
+
+    let res = candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    assert_eq!(
+        res.messages,
+        expected_mint(Some(format!("{}{}", TEST_BASE_URI, 1)))
+    );
+}
+
+#[test]
+fn fail_reveal_invalid_base_uri() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    match reveal(&mut deps, TEST_OWNER, TEST_BASE_URI).unwrap_err() {
+        ContractError::RevealNotCommitted {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    commit(&mut deps, TEST_OWNER).unwrap();
+
+    match reveal(&mut deps, TEST_OWNER, "ipfs://QmOther/").unwrap_err() {
+        ContractError::InvalidBaseUri {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    match reveal(&mut deps, TEST_USER_1, TEST_BASE_URI).unwrap_err() {
+        ContractError::Unauthorized {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    reveal(&mut deps, TEST_OWNER, TEST_BASE_URI).unwrap();
+
+    match reveal(&mut deps, TEST_OWNER, TEST_BASE_URI).unwrap_err() {
+        ContractError::AlreadyRevealed {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_commit_after_mint() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+    configure::set_nft_address(&mut deps, mock_env()).unwrap();
+
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    match commit(&mut deps, TEST_OWNER).unwrap_err() {
+        ContractError::RevealAlreadyStarted {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_commit_collectible() {
+    let mut deps = mock_deps();
+    let init_msg = InstantiateMsg {
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+    instantiate::exec(&mut deps, init_msg);
+
+    match commit(&mut deps, TEST_OWNER).unwrap_err() {
+        ContractError::InvalidCollectionKind {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
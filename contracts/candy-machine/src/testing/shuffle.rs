@@ -0,0 +1,344 @@
+use crate::msg::{
+    ConfigResponse, ConfigureShuffleMsg, ExecuteMsg, InstantiateMsg, MintStage, QueryMsg,
+    ShuffleResponse, StateResponse,
+};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, Response};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
+use crate::testing::instantiate;
+use crate::testing::{mock_deps, MockDeps, TEST_OWNER, TEST_STAGE_ID, TEST_USER_1};
+
+pub const TEST_SHUFFLE_SEED: &str = "candy-machine shuffle seed";
+// base64 encoded sha256 of TEST_SHUFFLE_SEED
+pub const TEST_SHUFFLE_SEED_HASH: &str = "NaKSi4pGvfe0Kldzz3QugOIB6EhRPJmbhaNqCjgbVd8=";
+const TEST_OTHER_SEED: &str = "candy-machine other seed";
+// base64 encoded sha256 of TEST_OTHER_SEED
+const TEST_OTHER_SEED_HASH: &str = "0H0drWLoaw/H6rU7edMVtGVRu5G7g2gfeZooamLjTTM=";
+const TEST_MAX_TOKEN_COUNT: u32 = 20;
+
+pub fn exec(
+    deps: &mut MockDeps,
+    sender: &str,
+    config: ConfigureShuffleMsg,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::ConfigureShuffle { config },
+    )
+}
+
+fn commit(deps: &mut MockDeps) -> Result<Response, ContractError> {
+    commit_hash(deps, TEST_SHUFFLE_SEED_HASH)
+}
+
+fn commit_hash(deps: &mut MockDeps, seed_hash: &str) -> Result<Response, ContractError> {
+    exec(
+        deps,
+        TEST_OWNER,
+        ConfigureShuffleMsg::Commit {
+            seed_hash: seed_hash.to_string(),
+        },
+    )
+}
+
+fn reveal(deps: &mut MockDeps, seed: &str) -> Result<Response, ContractError> {
+    exec(
+        deps,
+        TEST_OWNER,
+        ConfigureShuffleMsg::Reveal {
+            seed: seed.to_string(),
+        },
+    )
+}
+
+fn instantiate_shuffled(deps: &mut MockDeps) {
+    let init_msg = InstantiateMsg {
+        max_token_count: TEST_MAX_TOKEN_COUNT,
+        mint_stages: vec![MintStage {
+            max_per_user: None,
+            ..instantiate::default_mint_stage()
+        }],
+        ..instantiate::default_msg()
+    };
+
+    instantiate::exec(deps, init_msg);
+
+    configure::set_nft_address(deps, mock_env()).unwrap();
+}
+
+fn mint_quantity(deps: &mut MockDeps, sender: &str, quantity: u16) -> Response {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::Mint {
+            stage_id: TEST_STAGE_ID,
+            quantity: Some(quantity),
+            signatures: None,
+            merkle_proof: None,
+        },
+    )
+    .unwrap()
+}
+
+fn settle(deps: &mut MockDeps, limit: Option<u32>) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_USER_1, &[]),
+        ExecuteMsg::SettleMints { limit },
+    )
+}
+
+fn token_ids(res: &Response) -> Vec<u32> {
+    res.attributes
+        .iter()
+        .filter(|attr| attr.key == "token_ids")
+        .flat_map(|attr| attr.value.split(','))
+        .map(|token_id| token_id.parse().unwrap())
+        .collect()
+}
+
+// queues the same mints and settles them once the committed seed is revealed
+fn mint_shuffled(deps: &mut MockDeps, sender: &str, seed: &str, seed_hash: &str) -> Vec<u32> {
+    instantiate_shuffled(deps);
+    commit_hash(deps, seed_hash).unwrap();
+
+    for quantity in [1u16, 3, 7, 2, 5, 2].iter() {
+        let res = mint_quantity(deps, sender, *quantity);
+        assert!(token_ids(&res).is_empty());
+    }
+
+    reveal(deps, seed).unwrap();
+
+    let mut token_ids_settled = token_ids(&settle(deps, Some(4)).unwrap());
+    token_ids_settled.extend(token_ids(&settle(deps, None).unwrap()));
+    token_ids_settled
+}
+
+fn query_state(deps: &MockDeps) -> StateResponse {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    from_binary(&res).unwrap()
+}
+
+fn query_shuffle(deps: &MockDeps) -> Option<ShuffleResponse> {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let value: ConfigResponse = from_binary(&res).unwrap();
+    value.shuffle
+}
+
+#[test]
+fn success_shuffled_ids_assigned_once() {
+    let mut deps = mock_deps();
+    let mut token_ids = mint_shuffled(
+        &mut deps,
+        TEST_USER_1,
+        TEST_SHUFFLE_SEED,
+        TEST_SHUFFLE_SEED_HASH,
+    );
+    assert_eq!(
+        query_shuffle(&deps),
+        Some(ShuffleResponse {
+            seed_hash: TEST_SHUFFLE_SEED_HASH.to_string(),
+            revealed: true,
+        })
+    );
+    assert_eq!(query_state(&deps).queued, 0);
+
+    // ids are not handed out in order
+    let sequential: Vec<u32> = (1..=TEST_MAX_TOKEN_COUNT).collect();
+    assert_ne!(token_ids, sequential);
+
+    // but every id is assigned exactly once
+    token_ids.sort_unstable();
+    assert_eq!(token_ids, sequential);
+
+    match settle(&mut deps, None).unwrap_err() {
+        ContractError::NoMintsToSettle {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn success_ids_depend_on_unrevealed_seed() {
+    // everything readable before the reveal is the same, only the committed
+    // seed differs, so the ids cannot be computed in advance
+    let mut deps = mock_deps();
+    let token_ids = mint_shuffled(
+        &mut deps,
+        TEST_USER_1,
+        TEST_SHUFFLE_SEED,
+        TEST_SHUFFLE_SEED_HASH,
+    );
+    let mut other_deps = mock_deps();
+    let other_token_ids = mint_shuffled(
+        &mut other_deps,
+        TEST_USER_1,
+        TEST_OTHER_SEED,
+        TEST_OTHER_SEED_HASH,
+    );
+
+    assert_ne!(token_ids, other_token_ids);
+}
+
+#[test]
+fn success_ids_depend_on_recipient() {
+    let mut deps = mock_deps();
+    let token_ids = mint_shuffled(
+        &mut deps,
+        TEST_USER_1,
+        TEST_SHUFFLE_SEED,
+        TEST_SHUFFLE_SEED_HASH,
+    );
+    let mut other_deps = mock_deps();
+    let other_token_ids = mint_shuffled(
+        &mut other_deps,
+        TEST_OWNER,
+        TEST_SHUFFLE_SEED,
+        TEST_SHUFFLE_SEED_HASH,
+    );
+
+    assert_ne!(token_ids, other_token_ids);
+}
+
+#[test]
+fn success_queue_mints_until_reveal() {
+    let mut deps = mock_deps();
+    instantiate_shuffled(&mut deps);
+    commit(&mut deps).unwrap();
+
+    let res = mint_quantity(&mut deps, TEST_USER_1, 3);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "queue_mint"),
+            attr("owner", TEST_USER_1),
+            attr("stage_id", TEST_STAGE_ID.to_string()),
+            attr("queue_id", "0"),
+        ]
+    );
+    assert!(res.messages.is_empty());
+
+    let state = query_state(&deps);
+    assert_eq!(state.token_count, 3);
+    assert_eq!(state.queued, 3);
+
+    match settle(&mut deps, None).unwrap_err() {
+        ContractError::DropNotRevealed {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    reveal(&mut deps, TEST_SHUFFLE_SEED).unwrap();
+
+    let res = settle(&mut deps, None).unwrap();
+    assert_eq!(token_ids(&res).len(), 3);
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(query_state(&deps).queued, 0);
+}
+
+#[test]
+fn success_sequential_ids_without_shuffle() {
+    let mut deps = mock_deps();
+    instantiate_shuffled(&mut deps);
+
+    assert_eq!(query_shuffle(&deps), None);
+    assert_eq!(
+        token_ids(&mint_quantity(&mut deps, TEST_USER_1, 3)),
+        vec![1, 2, 3]
+    );
+}
+
+#[test]
+fn fail_mint_after_reveal() {
+    let mut deps = mock_deps();
+    instantiate_shuffled(&mut deps);
+
+    commit(&mut deps).unwrap();
+    reveal(&mut deps, TEST_SHUFFLE_SEED).unwrap();
+
+    match candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])) {
+        Ok(_) => panic!("Must return error"),
+        Err(ContractError::ShuffledSaleClosed {}) => (),
+        Err(e) => panic!("Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn fail_reveal_invalid_seed() {
+    let mut deps = mock_deps();
+    instantiate_shuffled(&mut deps);
+
+    match reveal(&mut deps, TEST_SHUFFLE_SEED).unwrap_err() {
+        ContractError::ShuffleSeedNotCommitted {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    commit(&mut deps).unwrap();
+
+    match reveal(&mut deps, "other seed").unwrap_err() {
+        ContractError::InvalidShuffleSeed {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    reveal(&mut deps, TEST_SHUFFLE_SEED).unwrap();
+
+    match reveal(&mut deps, TEST_SHUFFLE_SEED).unwrap_err() {
+        ContractError::ShuffleSeedAlreadyRevealed {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_commit_after_mint() {
+    let mut deps = mock_deps();
+    instantiate_shuffled(&mut deps);
+
+    candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+
+    match commit(&mut deps).unwrap_err() {
+        ContractError::ShuffleAlreadyStarted {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_commit_invalid_hash() {
+    let mut deps = mock_deps();
+    instantiate_shuffled(&mut deps);
+
+    let res = exec(
+        &mut deps,
+        TEST_OWNER,
+        ConfigureShuffleMsg::Commit {
+            seed_hash: base64::encode("short"),
+        },
+    );
+    match res.unwrap_err() {
+        ContractError::InvalidShuffleSeedHash {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_configure_shuffle_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate_shuffled(&mut deps);
+
+    let res = exec(
+        &mut deps,
+        TEST_USER_1,
+        ConfigureShuffleMsg::Commit {
+            seed_hash: TEST_SHUFFLE_SEED_HASH.to_string(),
+        },
+    );
+    match res.unwrap_err() {
+        ContractError::Unauthorized {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
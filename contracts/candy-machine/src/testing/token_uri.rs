@@ -0,0 +1,167 @@
+use crate::msg::{CollectionKind, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, to_binary, CosmosMsg, Response, SubMsg, WasmMsg};
+use cw721_metadata_onchain::{ExecuteMsg as Cw721ExecuteMsg, Metadata, MintMsg};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::testing::candy_machine;
+use crate::testing::configure;
+use crate::testing::instantiate;
+use crate::testing::{
+    mock_deps, MockDeps, TEST_MINTER, TEST_NFT_ADDRESS, TEST_NFT_DESCRIPTION, TEST_NFT_IMAGE,
+    TEST_NFT_NAME, TEST_OWNER, TEST_USER_1,
+};
+
+const TEST_BASE_URI: &str = "ipfs://QmMetadata/";
+const TEST_TOKEN_URI_SUFFIX: &str = ".json";
+
+pub fn exec(
+    deps: &mut MockDeps,
+    sender: &str,
+    base_uri: Option<&str>,
+    freeze: Option<bool>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::ConfigureBaseUri {
+            base_uri: base_uri.map(|base_uri| base_uri.to_string()),
+            token_uri_suffix: None,
+            freeze,
+        },
+    )
+}
+
+fn instantiate_with_base_uri(deps: &mut MockDeps, onchain_metadata: Option<bool>) {
+    let init_msg = InstantiateMsg {
+        collection_kind: CollectionKind::Single {
+            image: TEST_NFT_IMAGE.to_string(),
+            base_uri: Some(TEST_BASE_URI.to_string()),
+            token_uri_suffix: Some(TEST_TOKEN_URI_SUFFIX.to_string()),
+            onchain_metadata,
+            base_uri_frozen: None,
+        },
+        max_token_count: 5,
+        ..instantiate::default_msg()
+    };
+    instantiate::exec(deps, init_msg);
+
+    configure::set_nft_address(deps, mock_env()).unwrap();
+}
+
+fn metadata() -> Option<Metadata> {
+    Some(Metadata {
+        image: Some(TEST_NFT_IMAGE.to_string()),
+        description: Some(TEST_NFT_DESCRIPTION.into()),
+        name: Some(format!("{} #{}", TEST_NFT_NAME, 1)),
+        attributes: None,
+        ..Metadata::default()
+    })
+}
+
+fn mint_message(token_uri: &str, extension: Option<Metadata>) -> Vec<SubMsg> {
+    let mint_msg = MintMsg {
+        token_id: "1".to_string(),
+        owner: TEST_USER_1.to_string(),
+        token_uri: Some(token_uri.to_string()),
+        extension,
+    };
+
+    vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: TEST_NFT_ADDRESS.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Mint(mint_msg)).unwrap(),
+        funds: vec![],
+    }))]
+}
+
+#[test]
+fn success_mint_with_token_uri() {
+    let mut deps = mock_deps();
+    instantiate_with_base_uri(&mut deps, None);
+
+    let res = candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    assert_eq!(
+        res.messages,
+        mint_message("ipfs://QmMetadata/1.json", metadata())
+    );
+}
+
+#[test]
+fn success_mint_without_onchain_metadata() {
+    let mut deps = mock_deps();
+    instantiate_with_base_uri(&mut deps, Some(false));
+
+    let res = candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    assert_eq!(res.messages, mint_message("ipfs://QmMetadata/1.json", None));
+}
+
+#[test]
+fn success_update_base_uri_until_frozen() {
+    let mut deps = mock_deps();
+    instantiate_with_base_uri(&mut deps, None);
+
+    exec(&mut deps, TEST_OWNER, Some("ipfs://QmUpdated/"), None).unwrap();
+
+    let res = candy_machine::exec(&mut deps, mock_env(), mock_info(TEST_USER_1, &[])).unwrap();
+    assert_eq!(
+        res.messages,
+        mint_message("ipfs://QmUpdated/1.json", metadata())
+    );
+
+    exec(&mut deps, TEST_OWNER, None, Some(true)).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let value: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        value.collection_kind,
+        CollectionKind::Single {
+            image: TEST_NFT_IMAGE.to_string(),
+            base_uri: Some("ipfs://QmUpdated/".to_string()),
+            token_uri_suffix: Some(TEST_TOKEN_URI_SUFFIX.to_string()),
+            onchain_metadata: None,
+            base_uri_frozen: Some(true),
+        }
+    );
+
+    match exec(&mut deps, TEST_OWNER, Some(TEST_BASE_URI), None).unwrap_err() {
+        ContractError::BaseUriFrozen {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+    match exec(&mut deps, TEST_OWNER, None, Some(false)).unwrap_err() {
+        ContractError::BaseUriFrozen {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_configure_base_uri_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate_with_base_uri(&mut deps, None);
+
+    match exec(&mut deps, TEST_USER_1, Some(TEST_BASE_URI), None).unwrap_err() {
+        ContractError::Unauthorized {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_configure_base_uri_collectible() {
+    let mut deps = mock_deps();
+    let init_msg = InstantiateMsg {
+        collection_kind: CollectionKind::Collectible {
+            minter: TEST_MINTER.to_string(),
+            public_key: None,
+            key_type: None,
+            cover: TEST_NFT_IMAGE.to_string(),
+        },
+        ..instantiate::default_msg()
+    };
+    instantiate::exec(&mut deps, init_msg);
+
+    match exec(&mut deps, TEST_OWNER, Some(TEST_BASE_URI), None).unwrap_err() {
+        ContractError::InvalidCollectionKind {} => (),
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
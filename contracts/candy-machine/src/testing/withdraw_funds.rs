@@ -1,11 +1,34 @@
 use crate::msg::ExecuteMsg;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
-use cosmwasm_std::{attr, coins};
+use cosmwasm_std::{
+    attr, coin, coins, from_binary, to_binary, BankMsg, Coin, CosmosMsg, Response, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
 
 use crate::contract::execute;
 use crate::error::ContractError;
 use crate::testing::instantiate;
-use crate::testing::{mock_deps, TEST_OWNER, TEST_USER_1};
+use crate::testing::{mock_deps, MockDeps, TEST_CW20_ADDRESS, TEST_OWNER, TEST_USER_1};
+
+pub fn exec(
+    deps: &mut MockDeps,
+    sender: &str,
+    amount: Option<Vec<Coin>>,
+    cw20_tokens: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let info = mock_info(sender, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::WithdrawFunds {
+            recipient: TEST_OWNER.to_string(),
+            amount,
+            cw20_tokens,
+        },
+    )
+}
 
 #[test]
 fn success() {
@@ -17,41 +40,177 @@ fn success() {
     deps.querier
         .update_balance(MOCK_CONTRACT_ADDR, coins(amount, "uusd"));
 
-    let info = mock_info(TEST_OWNER, &[]);
-    let res = execute(
-        deps.as_mut(),
-        mock_env(),
-        info,
-        ExecuteMsg::WithdrawFunds {
-            recipient: TEST_OWNER.to_string(),
-        },
+    let res = exec(&mut deps, TEST_OWNER, None, None).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: TEST_OWNER.to_string(),
+            amount: coins(amount, "uusd"),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "withdraw_funds"),
+            attr("recipient", TEST_OWNER.to_string()),
+            attr("amount", "1000uusd"),
+        ]
+    );
+}
+
+#[test]
+fn success_all_denoms() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1000, "uluna"), coin(0, "ujuno"), coin(500, "uusd")],
+    );
+
+    let res = exec(&mut deps, TEST_OWNER, None, None).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: TEST_OWNER.to_string(),
+            amount: vec![coin(1000, "uluna"), coin(500, "uusd")],
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "withdraw_funds"),
+            attr("recipient", TEST_OWNER.to_string()),
+            attr("amount", "1000uluna,500uusd"),
+        ]
+    );
+}
+
+#[test]
+fn success_requested_amount() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1000, "uluna"), coin(500, "uusd")],
+    );
+
+    let res = exec(&mut deps, TEST_OWNER, Some(coins(200, "uusd")), None).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: TEST_OWNER.to_string(),
+            amount: coins(200, "uusd"),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "withdraw_funds"),
+            attr("recipient", TEST_OWNER.to_string()),
+            attr("amount", "200uusd"),
+        ]
+    );
+}
+
+#[test]
+fn success_cw20_tokens() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(500, "uusd"));
+    deps.querier.register_wasm_smart_query_handler(
+        TEST_CW20_ADDRESS.to_string(),
+        Box::new(|msg| match from_binary(msg).unwrap() {
+            Cw20QueryMsg::Balance { address } => {
+                assert_eq!(address, MOCK_CONTRACT_ADDR);
+                to_binary(&BalanceResponse {
+                    balance: Uint128::from(300u128),
+                })
+            }
+            _ => panic!("DO NOT ENTER HERE"),
+        }),
+    );
+
+    let res = exec(
+        &mut deps,
+        TEST_OWNER,
+        Some(vec![]),
+        Some(vec![TEST_CW20_ADDRESS.to_string()]),
     )
     .unwrap();
 
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: TEST_CW20_ADDRESS.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_OWNER.to_string(),
+                amount: Uint128::from(300u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
     assert_eq!(
         res.attributes,
         vec![
             attr("action", "withdraw_funds"),
             attr("recipient", TEST_OWNER.to_string()),
+            attr("cw20_amount", format!("300{}", TEST_CW20_ADDRESS)),
         ]
     );
 }
 
 #[test]
-fn fail_unauthorized() {
+fn fail_insufficient_funds() {
     let mut deps = mock_deps();
     instantiate::default(&mut deps);
 
-    let info = mock_info(TEST_USER_1, &[]);
-    let res = execute(
-        deps.as_mut(),
-        mock_env(),
-        info,
-        ExecuteMsg::WithdrawFunds {
-            recipient: TEST_OWNER.to_string(),
-        },
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(500, "uusd"));
+
+    let res = exec(
+        &mut deps,
+        TEST_OWNER,
+        Some(vec![coin(100, "uusd"), coin(100, "uluna")]),
+        None,
     );
 
+    match res.unwrap_err() {
+        ContractError::InsufficientFunds { denom, available } => {
+            assert_eq!(denom, "uluna");
+            assert_eq!(available, Uint128::zero());
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_no_funds_to_withdraw() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let res = exec(&mut deps, TEST_OWNER, None, None);
+
+    match res.unwrap_err() {
+        ContractError::NoFundsToWithdraw {} => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn fail_unauthorized() {
+    let mut deps = mock_deps();
+    instantiate::default(&mut deps);
+
+    let res = exec(&mut deps, TEST_USER_1, None, None);
+
     match res.unwrap_err() {
         ContractError::Unauthorized {} => {}
         e => panic!("unexpected error: {:?}", e),